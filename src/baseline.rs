@@ -0,0 +1,231 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use anyhow::*;
+use std::result::Result::Ok;
+use itertools::Itertools;
+use crate::Year;
+use crate::benchmark::{BenchmarkResult, DayBenchmark, DurationStats};
+use crate::timing::{Phase, PhaseDurations};
+
+/// Default directory where the benchmark baselines are stored, as `<dir>/<label>/<yyyy>.tsv`
+pub const DEFAULT_BASELINES_DIR: &str = "baselines";
+
+/// Default relative slowdown, in percent, beyond which a day is flagged as a regression
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Header of the baseline files. The columns are separated by tabulations, as some answers contain commas.
+const HEADER: &str = "day\tpart_a\tpart_b\tsamples\tmean_ns\tstd_dev_ns\tmin_ns\tmedian_ns\tp90_ns\tp99_ns\tmax_ns\
+                      \tparse_ns\tpart_a_ns\tpart_b_ns\tboth_parts_ns";
+
+/// Outcome of the comparison of a single day with the baseline
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trend {
+    Regressed,
+    Improved,
+    Stable,
+}
+
+/// Comparison of a single day with the baseline
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub day: u32,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub trend: Trend,
+
+    /// The answers differ from the baseline ones
+    pub answers_changed: bool,
+}
+
+/// Get a label identifying the current state of the code: the git revision, suffixed by `-dirty`
+/// if there are uncommitted changes.
+pub fn default_label () -> Result<String> {
+
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git").args(args).output()
+            .map_err(|err| anyhow!("Cannot run git: {}", err))?;
+        if !output.status.success() { bail!("Cannot get the git revision, please provide a label") }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let revision = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Ok(if dirty { format!("{}-dirty", revision) } else { revision })
+}
+
+/// Location of the baseline of the `year` saved under the given `label`
+fn baseline_path (baselines_dir: &Path, label: &str, year: u32) -> Result<PathBuf> {
+    if label.is_empty() || label.contains(['/', '\\']) || label.starts_with('.') {
+        bail!("Invalid baseline label '{}'", label);
+    }
+    Ok(baselines_dir.join(label).join(format!("{}.tsv", year)))
+}
+
+/// Save the successful days of the `benchmark_result` of the `year` as the baseline `label`.
+/// The days of a previous baseline with the same label that were not benchmarked again are kept.
+pub fn save_baseline<Y> (year: &Y, benchmark_result: &BenchmarkResult, baselines_dir: &Path, label: &str) -> Result<PathBuf>
+where Y : Year + ?Sized {
+
+    let path = baseline_path(baselines_dir, label, year.get_year())?;
+    let mut baseline = if path.exists() { read_baseline(&path)? } else { BenchmarkResult::new() };
+
+    for (&day, day_benchmark) in benchmark_result.iter() {
+        if let Ok(day_benchmark) = day_benchmark {
+            baseline.insert(day, Ok(day_benchmark.clone()));
+        }
+    }
+
+    let rows = baseline.iter().sorted_by_key(|(&day, _)| day).filter_map(|(&day, day_benchmark)| {
+        let DayBenchmark { answers, stats, phases } = day_benchmark.as_ref().ok()?;
+        let durations = [stats.mean, stats.std_dev, stats.min, stats.median, stats.p90, stats.p99, stats.max]
+            .into_iter()
+            .chain(Phase::iter().map(|phase| phases.get(phase)))
+            .map(|duration| duration.as_nanos().to_string());
+
+        let row = [day.to_string(), answers.0.clone(), answers.1.clone(), stats.samples.to_string()]
+            .into_iter()
+            .chain(durations)
+            .join("\t");
+        Some(row)
+    });
+    let content: String = std::iter::once(HEADER.to_string()).chain(rows).map(|row| row + "\n").collect();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, content)
+        .map_err(|err| anyhow!("Failed to save baseline to {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+/// Load the baseline of the `year` saved under the given `label`
+pub fn load_baseline<Y> (year: &Y, baselines_dir: &Path, label: &str) -> Result<BenchmarkResult>
+where Y : Year + ?Sized {
+    read_baseline(&baseline_path(baselines_dir, label, year.get_year())?)
+}
+
+/// Read the baseline file at `path`
+fn read_baseline (path: &Path) -> Result<BenchmarkResult> {
+
+    let content = fs::read_to_string(path)
+        .map_err(|err| anyhow!("Failed to read baseline {}: {}", path.display(), err))?;
+
+    content.lines().skip(1).filter(|row| !row.is_empty()).map(|row| {
+        parse_row(row).map_err(|err| anyhow!("Invalid baseline {}: {}", path.display(), err))
+    }).collect()
+}
+
+/// Parse a single `row` of a baseline file
+fn parse_row (row: &str) -> Result<(u32, Result<DayBenchmark>)> {
+
+    let columns = row.split('\t').collect_vec();
+    if columns.len() != HEADER.split('\t').count() { bail!("Wrong number of columns in row '{}'", row) }
+
+    let number = |idx: usize| -> Result<u128> {
+        columns [idx].parse().map_err(|_| anyhow!("Invalid number '{}'", columns [idx]))
+    };
+    let nanos = |idx: usize| -> Result<Duration> {
+        Ok(Duration::from_nanos(number(idx)? as u64))
+    };
+
+    let stats = DurationStats {
+        samples: number(3)? as usize,
+        mean: nanos(4)?,
+        std_dev: nanos(5)?,
+        min: nanos(6)?,
+        median: nanos(7)?,
+        p90: nanos(8)?,
+        p99: nanos(9)?,
+        max: nanos(10)?,
+    };
+
+    let mut phases = PhaseDurations::default();
+    for (idx, phase) in Phase::iter().enumerate() {
+        *phases.get_mut(phase) = nanos(11 + idx)?;
+    }
+
+    let answers = (columns [1].to_string(), columns [2].to_string());
+    Ok((number(0)? as u32, Ok(DayBenchmark { answers, stats, phases })))
+}
+
+/// Compare the `current` benchmark with the `baseline`, for the days benchmarked successfully in both.
+/// A day is flagged as regressed (or improved) when its mean execution time increased (or decreased)
+/// by more than `threshold` percent.
+pub fn compare (current: &BenchmarkResult, baseline: &BenchmarkResult, threshold: f64) -> Vec<Comparison> {
+
+    let ratio = 1.0 + threshold / 100.0;
+    current.keys().sorted().filter_map(|day| {
+        let Some(Ok(current)) = current.get(day) else { return None };
+        let Some(Ok(baseline)) = baseline.get(day) else { return None };
+
+        let (now, before) = (current.stats.mean.as_secs_f64(), baseline.stats.mean.as_secs_f64());
+        let trend = if now > before * ratio { Trend::Regressed }
+        else if now * ratio < before { Trend::Improved }
+        else { Trend::Stable };
+
+        Some(Comparison {
+            day: *day,
+            baseline: baseline.stats.mean,
+            current: current.stats.mean,
+            trend,
+            answers_changed: current.answers != baseline.answers,
+        })
+    }).collect()
+}
+
+/// Print the `comparisons` of the provided `year` module with the baseline `label`
+pub fn print_comparisons<Y> (year: &Y, label: &str, comparisons: &[Comparison])
+where Y : Year + ?Sized {
+
+    println!();
+    println!("Comparison of year {} with baseline '{}' (ms):", year.get_year(), label);
+    for comparison in comparisons {
+
+        let before = comparison.baseline.as_secs_f64() * 1000.0;
+        let now = comparison.current.as_secs_f64() * 1000.0;
+        let change = if before > 0.0 { (now - before) / before * 100.0 } else { 0.0 };
+        let flag = match comparison.trend {
+            Trend::Regressed => "REGRESSED",
+            Trend::Improved => "improved",
+            Trend::Stable => "",
+        };
+        let answers = if comparison.answers_changed { " (answers changed)" } else { "" };
+
+        println!(" {:02} - {:<25} | {:>10.3} -> {:>10.3} | {:>+7.1}% {}{}",
+                 comparison.day, year.get_day_name(comparison.day).unwrap_or_default(),
+                 before, now, change, flag, answers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make a benchmark of a single day, lasting `mean_ms` on average
+    fn day_benchmark (mean_ms: u64) -> Result<DayBenchmark> {
+        let stats = DurationStats { samples: 10, mean: Duration::from_millis(mean_ms), ..Default::default() };
+        Ok(DayBenchmark { answers: ("1".to_string(), "2,3".to_string()), stats, phases: PhaseDurations::default() })
+    }
+
+    #[test]
+    fn test_parse_row () {
+        let (day, day_benchmark) = parse_row("7\t1\t2,3\t10\t5000\t1\t2\t3\t4\t5\t6\t7\t8\t9\t10").unwrap();
+        let day_benchmark = day_benchmark.unwrap();
+        assert_eq!(day, 7);
+        assert_eq!(day_benchmark.answers, ("1".to_string(), "2,3".to_string()));
+        assert_eq!(day_benchmark.stats.mean, Duration::from_nanos(5000));
+        assert_eq!(day_benchmark.phases.both_parts, Duration::from_nanos(10));
+        assert!(parse_row("7\t1\t2").is_err());
+    }
+
+    #[test]
+    fn test_compare () {
+        let baseline = BenchmarkResult::from([(1, day_benchmark(100)), (2, day_benchmark(100)), (3, day_benchmark(100))]);
+        let current = BenchmarkResult::from([(1, day_benchmark(105)), (2, day_benchmark(120)), (3, day_benchmark(50)), (4, day_benchmark(1))]);
+
+        let trends = compare(&current, &baseline, 10.0).iter().map(|c| (c.day, c.trend)).collect_vec();
+        assert_eq!(trends, [(1, Trend::Stable), (2, Trend::Regressed), (3, Trend::Improved)]);
+    }
+}
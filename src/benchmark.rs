@@ -1,80 +1,329 @@
 use std::collections::HashMap;
-use std::io::{stdout, Write};
-use std::ops::Div;
-use std::time::Duration;
+use std::io::{stderr, Write};
+use std::ops::{Div, RangeBounds};
+use std::time::{Duration, Instant};
 use itertools::Itertools;
+use rayon::prelude::*;
 use svg::Document;
-use svg::node::element::{Group, Rectangle, Text, LinearGradient, Stop, Line, Animate};
-use crate::{solve_day, Year};
+use svg::node::element::{Group, Rectangle, Text, LinearGradient, Stop, Line, Animate, Title};
+use crate::{solve_lines, Year};
+use crate::input::InputSource;
+use crate::timing::{Phase, PhaseDurations};
+use crate::params::Params;
 
 
 /// The result of performance benchmarking, indexed on the day numbers.
-pub type BenchmarkResult = HashMap<u32, anyhow::Result<Duration>>;
+pub type BenchmarkResult = HashMap<u32, anyhow::Result<DayBenchmark>>;
 
-/// Raw result of performance benchmarking, as execution times indexed on the day numbers.
-type BenchmarkRawResult = HashMap<u32, anyhow::Result<Vec<Duration>>>;
+/// Raw result of performance benchmarking, as answers and execution times of each phase, indexed on the day numbers.
+type BenchmarkRawResult = HashMap<u32, anyhow::Result<(Answers, Vec<PhaseDurations>)>>;
 
-/// Do a benchmark of the provided `year`. The function execute each daily puzzle in turn,
-/// and repeat the operation multiple times according to the parameter `num_repetitions`.
-pub fn benchmark_year<Y> (year: &Y, num_repetitions: usize) -> BenchmarkResult
-where Y : Year {
+/// The answers to both parts of a puzzle, as text
+type Answers = (String, String);
+
+/// Performance benchmarking of a single day
+#[derive(Debug, Clone)]
+pub struct DayBenchmark {
+
+    /// The answers found to both parts
+    pub answers: Answers,
+
+    /// Statistics on the execution time
+    pub stats: DurationStats,
+
+    /// The execution time of each phase, as means
+    pub phases: PhaseDurations,
+}
+
+/// Statistics on the execution times measured for a single day
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct DurationStats {
+
+    /// Number of measurements
+    pub samples: usize,
+
+    /// Mean of the measurements, possibly excluding the outliers
+    pub mean: Duration,
+
+    /// Standard deviation of the measurements, possibly excluding the outliers
+    pub std_dev: Duration,
+
+    pub min: Duration,
+    pub median: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// How to run a benchmark
+#[derive(Debug, Copy, Clone)]
+pub struct BenchmarkOptions {
 
-    println!("Benchmark year {:?}: ", year.get_year());
+    /// Number of times each puzzle is solved before starting the measurements
+    pub num_warmups: usize,
+
+    /// Number of times each puzzle is solved and measured
+    pub num_repetitions: usize,
+
+    /// Exclude the 10% topmost and 10% bottommost measurements from the mean and standard deviation
+    pub trim_outliers: bool,
+}
+
+/// Do a benchmark of the provided `year`. The function execute each daily puzzle in turn,
+/// and repeat the operation multiple times according to the `options`.
+/// Only the days in `day_range` are considered, and their puzzle inputs are read from `input`.
+/// The default values of the puzzle parameters are replaced by the `overrides` (name and value).
+///
+/// The puzzle inputs are loaded once, before any measurement. Then the puzzles are solved
+/// a few times without being measured, so that the caches are warm when measuring.
+pub fn benchmark_year<Y> (
+    year: &Y,
+    day_range: impl RangeBounds<u32>,
+    options: &BenchmarkOptions,
+    overrides: &[(String, String)],
+    input: &InputSource) -> BenchmarkResult
+where Y : Year + ?Sized {
+
+    let BenchmarkOptions { num_warmups, num_repetitions, trim_outliers } = *options;
+
+    eprintln!("Benchmark year {:?}: ", year.get_year());
 
     let mut raw_durations = BenchmarkRawResult::new();
 
-    for idx in 0..num_repetitions {
+    // Load the inputs of the days to benchmark, so that no file is read while measuring
+    let mut day_inputs = vec![];
+    for day in (1..=25).filter(|day| day_range.contains(day)) {
+
+        // Get the function related to the current day, or skip the test
+        let Some(fn_solve) = year.get_day_fn(day) else { continue };
 
-        if idx% 10 == 0 { print!("#"); }
-        else { print!("."); }
-        stdout().flush().expect("TODO: panic message");
+        let params = Params::with_overrides(year.get_day_params(day), overrides);
+        match params.and_then(|params| Ok((params, input.load(year.get_year(), day)?))) {
+            Ok((params, lines)) => day_inputs.push((day, fn_solve, params, lines)),
+            Err(err) => { raw_durations.insert(day, Err(err)); }
+        }
+    }
+
+    for idx in 0..num_warmups + num_repetitions {
 
-        for day in 1..=25 {
+        // The first iterations are only warming up, and are not measured
+        let warming_up = idx < num_warmups;
+        let idx = idx.wrapping_sub(num_warmups);
 
-            // Get the function related to the current day, or skip the test
-            let Some(fn_solve) = year.get_day_fn(day) else { continue };
+        if warming_up { eprint!("-"); }
+        else if idx% 10 == 0 { eprint!("#"); }
+        else { eprint!("."); }
+        stderr().flush().expect("TODO: panic message");
 
-            // Also skip if failed in previous iteration
-            let day_entry = raw_durations.entry(day).or_insert_with(|| Ok(vec![]));
-            let Ok(day_duration) = day_entry else { continue };
+        for (day, fn_solve, params, lines) in day_inputs.iter() {
 
-            // Solve and collect the solving time, or the error
-            match solve_day(year.get_year(), day, fn_solve) {
-                Ok((_a, _b, duration)) => { day_duration.push(duration); }
+            // Skip if failed in previous iteration
+            let day_entry = raw_durations.entry(*day).or_insert_with(|| Ok(Default::default()));
+            let Ok((answers, day_duration)) = day_entry else { continue };
+
+            // Solve and collect the answers and solving time, or the error
+            match solve_lines(*fn_solve, params, lines) {
+                Ok((a, b, durations)) => {
+                    *answers = (a.to_string(), b.to_string());
+                    if !warming_up { day_duration.push(durations); }
+                }
                 Err(err) => { *day_entry = Err(err) }
             };
         }
     }
+    eprintln!();
 
-    // Take each vector of measurement and compute a trimmed mean
+    // Take each vector of measurement and compute the statistics of the total, and the mean of each phase
     raw_durations.into_iter().map(
         |(day, duration_or_err)| (
             day,
-            duration_or_err.map(|d| trimmed_mean (&d))
+            duration_or_err.map(|(answers, d)| {
+                let mut phases = PhaseDurations::default();
+                for phase in Phase::iter() {
+                    let phase_data = d.iter().map(|p| p.get(phase)).sorted().collect_vec();
+                    *phases.get_mut(phase) = mean(trim(&phase_data, trim_outliers));
+                }
+                let stats = DurationStats::new(&d.iter().map(|p| p.total()).collect_vec(), trim_outliers);
+                DayBenchmark { answers, stats, phases }
+            })
         )
     ).collect()
 }
 
-/// Compute a mean of the execution time vector `data`, excluding the 10% topmost and 10%
-/// bottommost outliers.
-fn trimmed_mean (data: &[Duration]) -> Duration {
+/// Wall-clock benchmark of a whole year, whose days are solved concurrently
+#[derive(Debug)]
+pub struct YearBenchmark {
+
+    /// Number of threads the days are dispatched to
+    pub num_threads: usize,
+
+    /// Number of days solved in each run
+    pub num_days: usize,
+
+    /// Statistics on the time to solve all the days
+    pub stats: DurationStats,
+
+    /// The days excluded from the benchmark, because they failed
+    pub failed_days: Vec<(u32, anyhow::Error)>,
+}
+
+/// Do a multi-threaded benchmark of the provided `year`: all the days in `day_range` are solved
+/// concurrently, and the wall-clock time to solve them all is measured. The operation is repeated
+/// according to the `options`, and the puzzle inputs are read once from `input`. The default values
+/// of the puzzle parameters are replaced by the `overrides` (name and value).
+///
+/// The days that fail on a first run are excluded from the measurements.
+pub fn benchmark_year_parallel<Y> (
+    year: &Y,
+    day_range: impl RangeBounds<u32>,
+    options: &BenchmarkOptions,
+    overrides: &[(String, String)],
+    input: &InputSource) -> YearBenchmark
+where Y : Year + ?Sized {
+
+    let BenchmarkOptions { num_warmups, num_repetitions, trim_outliers } = *options;
+
+    eprintln!("Parallel benchmark year {:?}: ", year.get_year());
+
+    // Load the inputs of the days to benchmark, and check that they can be solved
+    let mut failed_days = vec![];
+    let mut day_inputs = vec![];
+    for day in (1..=25).filter(|day| day_range.contains(day)) {
+
+        // Get the function related to the current day, or skip the test
+        let Some(fn_solve) = year.get_day_fn(day) else { continue };
+
+        let loaded = Params::with_overrides(year.get_day_params(day), overrides).and_then(|params| {
+            let lines = input.load(year.get_year(), day)?;
+            solve_lines(fn_solve, &params, &lines)?;
+            Ok((params, lines))
+        });
+        match loaded {
+            Ok((params, lines)) => day_inputs.push((fn_solve, params, lines)),
+            Err(err) => failed_days.push((day, err)),
+        }
+    }
 
-    let trim_size = data.len() / 10;
-    let trimmed_data_len = data.len()-trim_size*2;
+    let mut durations = vec![];
+    for idx in 0..num_warmups + num_repetitions {
 
-    let sorted: Duration = data.iter()
-        .sorted()
-        .skip(trim_size)
-        .take(trimmed_data_len)
-        .sum();
+        // The first iterations are only warming up, and are not measured
+        let warming_up = idx < num_warmups;
+        if warming_up { eprint!("-"); } else { eprint!("."); }
+        stderr().flush().expect("TODO: panic message");
 
-    sorted.div(trimmed_data_len as u32)
+        let start = Instant::now();
+        day_inputs.par_iter().for_each(|(fn_solve, params, lines)| { let _ = solve_lines(*fn_solve, params, lines); });
+        if !warming_up { durations.push(start.elapsed()); }
+    }
+    eprintln!();
+
+    YearBenchmark {
+        num_threads: rayon::current_num_threads(),
+        num_days: day_inputs.len(),
+        stats: DurationStats::new(&durations, trim_outliers),
+        failed_days,
+    }
 }
 
-pub fn make_svg (benchmark_result: &BenchmarkResult, out_path: &str) {
+impl DurationStats {
+
+    /// Compute the statistics of the execution time vector `data`. The mean and the standard
+    /// deviation exclude the 10% topmost and 10% bottommost outliers if `trim_outliers` is set.
+    pub fn new (data: &[Duration], trim_outliers: bool) -> DurationStats {
+
+        if data.is_empty() { return DurationStats::default() }
+
+        let sorted = data.iter().copied().sorted().collect_vec();
+        let trimmed = trim(&sorted, trim_outliers);
+        let mean = mean(trimmed);
+
+        let variance = trimmed.iter().map(|d| {
+            let diff = d.as_secs_f64() - mean.as_secs_f64();
+            diff * diff
+        }).sum::<f64>() / trimmed.len() as f64;
+
+        DurationStats {
+            samples: data.len(),
+            mean,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            min: sorted [0],
+            median: percentile(&sorted, 50),
+            p90: percentile(&sorted, 90),
+            p99: percentile(&sorted, 99),
+            max: sorted [sorted.len()-1],
+        }
+    }
+}
+
+/// Exclude the 10% topmost and 10% bottommost outliers of the `sorted` execution times,
+/// if `trim_outliers` is set.
+fn trim (sorted: &[Duration], trim_outliers: bool) -> &[Duration] {
+    let trim_size = if trim_outliers { sorted.len() / 10 } else { 0 };
+    &sorted [trim_size..sorted.len()-trim_size]
+}
+
+/// Compute the mean of the execution time vector `data`
+fn mean (data: &[Duration]) -> Duration {
+    if data.is_empty() { return Duration::ZERO }
+    data.iter().sum::<Duration>().div(data.len() as u32)
+}
+
+/// Get the `p`-th percentile of the `sorted` execution times, with the nearest-rank method
+fn percentile (sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted [rank-1]
+}
+
+/// A named benchmark result of some `year` to render in a chart, e.g. the current run or a saved baseline
+pub struct Series<'a> {
+    pub label: String,
+    pub year: &'a dyn Year,
+    pub result: BenchmarkResult,
+}
+
+/// Colors of the bars of each series, when a chart renders several of them
+const SERIES_COLORS: [&str; 5] = ["rgb(25,140,140)", "rgb(255,70,10)", "rgb(50,180,80)", "rgb(30,30,100)", "rgb(255,240,100)"];
+
+/// Render the `series` as histograms with a logarithmic scale, into the SVG file `out_path`.
+/// The bars of the different series are grouped by day, and the scale is adapted to the
+/// range of the execution times. When `error_bars` is set, each bar displays the standard
+/// deviation around the mean. Hovering a bar shows the puzzle name and the statistics, and the
+/// days that failed are rendered as a red cross.
+pub fn make_svg (series: &[Series], out_path: &str, error_bars: bool) {
+
+    // All the days benchmarked in at least one series, and the range of their execution times
+    let days = series.iter()
+        .flat_map(|s| s.result.keys().copied())
+        .unique().sorted().collect_vec();
+    let all_stats = series.iter().flat_map(|s| s.result.values().filter_map(|r| r.as_ref().ok()).map(|b| b.stats));
+    let (fastest, slowest) = all_stats.fold((f64::MAX, f64::MIN_POSITIVE), |(fastest, slowest), stats| {
+        let (low, high) = match error_bars {
+            true => (stats.mean.saturating_sub(stats.std_dev).max(stats.min), stats.mean + stats.std_dev),
+            false => (stats.mean, stats.mean),
+        };
+        (fastest.min(low.as_secs_f64().max(1e-9)), slowest.max(high.as_secs_f64()))
+    });
+    let (low_decade, high_decade) = match fastest <= slowest {
+        true => decade_range(fastest, slowest),
+        false => (-4, 0),   // No successful day at all, from 100 µs to 1 s
+    };
+    let num_decades = high_decade - low_decade;
+
+    let num_slots = days.len().max(1) as i32;
+    let num_series = series.len().max(1) as i32;
+    let bar_width: f32 = if num_series == 1 { 28.0 } else { 16.0 };
+    let slot_width = bar_width * num_series as f32 * 1.5;
 
-    let svg_width = 1024;
     let svg_height = 512;
+    let margin_left = 70;
+    let margin_right = 4;
+    let margin_top = svg_height / 10;
+    let margin_bottom = svg_height / 20;
+    let histo_width = ((slot_width * num_slots as f32) as i32).max(1024 - margin_left - margin_right);
+    let histo_height = svg_height-margin_top-margin_bottom;
+    let svg_width = margin_left + histo_width + margin_right;
+    let slot_width = histo_width as f32 / num_slots as f32;
 
     // Création du document SVG
     let mut document = Document::new()
@@ -83,18 +332,11 @@ pub fn make_svg (benchmark_result: &BenchmarkResult, out_path: &str) {
         .set("height", svg_height)
         .set("xmlns", "http://www.w3.org/2000/svg");
 
-    let margin_left = svg_width / 15;
-    let margin_top = svg_height / 10;
-    let margin_bottom = svg_height / 20;
-    let histo_width = svg_width-margin_left-4;
-    let histo_height = svg_height-margin_top-margin_bottom;
-
-    let num_bars = *benchmark_result.keys().max().unwrap();
-    let bar_width = histo_width as f32 / num_bars as f32;
-    let space = bar_width / 2.0;
-    let new_width = histo_width as f32 + space * num_bars as f32;
-    let bar_width = bar_width * histo_width as f32 / new_width;
-    let space = space * histo_width as f32 / new_width;
+    // Height of a bar representing the given `duration`, on the log scale
+    let bar_height_of = |duration: Duration| {
+        let y = duration.as_secs_f64().max(1e-9).log10() - low_decade as f64;
+        (y.max(0.0) * histo_height as f64 / num_decades as f64) as f32
+    };
 
     let mut group = Group::new();
 
@@ -116,12 +358,11 @@ pub fn make_svg (benchmark_result: &BenchmarkResult, out_path: &str) {
         .set("stroke", "black");
     group = group.add(graph_background);
 
-    let labels = ["100 µs", "1 ms", "10 ms", "100 ms", "1s"];
-    for y in 1..=5 {
+    for y in 1..=num_decades {
 
-        let y_pos = svg_height - margin_bottom - y * histo_height / 5;
+        let y_pos = svg_height - margin_bottom - y * histo_height / num_decades;
 
-        let text = Text::new(labels [y as usize -1])
+        let text = Text::new(decade_label(low_decade + y))
             .set("x", margin_left-4)
             .set("y", y_pos)
             .set("text-anchor", "end")
@@ -131,11 +372,11 @@ pub fn make_svg (benchmark_result: &BenchmarkResult, out_path: &str) {
             .set("fill", "black");
         group = group.add(text);
 
-        if y < 5 {
+        if y < num_decades {
             let line = Line::new()
                 .set("x1", margin_left)
                 .set("y1", y_pos)
-                .set("x2", svg_width - 4)
+                .set("x2", svg_width - margin_right)
                 .set("y2", y_pos)
                 .set("stroke-width", "1")
                 .set("stroke", "rgb(150,150,150)");
@@ -176,47 +417,259 @@ pub fn make_svg (benchmark_result: &BenchmarkResult, out_path: &str) {
         );
     group = group.add(gradient);
 
-    for (&day, duration) in benchmark_result.iter() {
-        let Ok(duration) = duration else { continue };
-
-        let id = format!("day{:02}", day);
-        let y = (duration.as_micros() as f32 / 10.0).log10().max(0.0);
-        let bar_height = (y * histo_height as f32 / 5.0) as i32;
-        let x_position = margin_left + (space / 2.0 + (day-1) as f32 * (space + bar_width)) as i32;
-        let y_position = margin_top + histo_height - bar_height;
-
-        let bar = Rectangle::new()
-            .set("id", id.clone())
-            .set("x", x_position)
-            .set("y", y_position)
-            .set("width", bar_width)
-            .set("height", bar_height)
-            .set("style", "cursor: pointer;")
-            .set("fill", "url(#gradient)")
-            .add(
-                Animate::new()
-                    .set("attributeName", "fill")
-                    .set("from", "#ec008c")
-                    .set("to", "#ec008c")
-                    .set("begin", format!("{}.mouseover", &id))
-                    .set("end", format!("{}.mouseout", &id))
-                    .set("dur", "0s"),
-            );
+    // A single series is rendered with the gradient, several ones with a color each
+    let series_fill = |idx: usize| match series.len() {
+        1 => "url(#gradient)".to_string(),
+        _ => SERIES_COLORS [idx % SERIES_COLORS.len()].to_string(),
+    };
+
+    // Legend of the series on the left, if there are several of them, and of the phases on the right
+    let legend_size = margin_top / 3;
+    let legend_y = margin_top / 2 - legend_size / 2;
+    let legend_swatch = |group: Group, x: i32, fill: &str, shade: Option<&str>, label: &str| {
+        let mut group = group.add(Rectangle::new()
+            .set("x", x)
+            .set("y", legend_y)
+            .set("width", legend_size)
+            .set("height", legend_size)
+            .set("fill", fill));
+        if let Some(shade) = shade {
+            group = group.add(Rectangle::new()
+                .set("x", x)
+                .set("y", legend_y)
+                .set("width", legend_size)
+                .set("height", legend_size)
+                .set("fill", shade));
+        }
+        group.add(Text::new(label)
+            .set("x", x + legend_size * 5 / 4)
+            .set("y", margin_top / 2)
+            .set("dominant-baseline", "middle")
+            .set("font-size", margin_bottom * 6 / 10)
+            .set("fill", "black"))
+    };
+
+    if series.len() > 1 {
+        let mut legend_x = margin_left;
+        for (idx, s) in series.iter().enumerate() {
+            group = legend_swatch(group, legend_x, &series_fill(idx), None, &s.label);
+            legend_x += legend_size * 2 + s.label.chars().count() as i32 * legend_size * 2 / 3;
+        }
+    }
+
+    let mut legend_x = svg_width - margin_right;
+    for phase in Phase::iter().collect_vec().into_iter().rev() {
+        legend_x -= legend_size * 6;
+        group = legend_swatch(group, legend_x, "rgb(50,180,80)", phase_shade(phase), phase.name());
+    }
+
+    for (slot, &day) in days.iter().enumerate() {
+
+        let slot_x = margin_left as f32 + slot as f32 * slot_width;
+        let bars_x = slot_x + (slot_width - bar_width * num_series as f32) / 2.0;
 
         let text = Text::new(day.to_string())
-            .set("x", x_position + bar_width as i32 / 2)
+            .set("x", slot_x + slot_width / 2.0)
             .set("y", svg_height - margin_bottom/2)
             .set("text-anchor", "middle")
             .set("dominant-baseline", "middle")
             .set("font-size", margin_bottom * 6 / 10)
             .set("fill", "black");
+        group = group.add(text);
+
+        for (idx, s) in series.iter().enumerate() {
+
+            let x_position = bars_x + idx as f32 * bar_width;
+            let puzzle = format!("{} - day {} - {}", s.label, day, s.year.get_day_name(day).unwrap_or_default());
+
+            let (stats, phases) = match s.result.get(&day) {
+                None => continue,
+                Some(Ok(DayBenchmark { stats, phases, .. })) => (stats, phases),
+                Some(Err(err)) => {
+                    group = group.add(error_marker(x_position, (margin_top + histo_height) as f32, bar_width)
+                        .add(Title::new(format!("{}\nERROR: {}", puzzle, err))));
+                    continue
+                }
+            };
+
+            let id = format!("s{}-day{:02}", idx, day);
+            let bar_height = bar_height_of(stats.mean);
+            let y_position = (margin_top + histo_height) as f32 - bar_height;
+
+            let bar = Rectangle::new()
+                .set("id", id.clone())
+                .set("x", x_position)
+                .set("y", y_position)
+                .set("width", bar_width)
+                .set("height", bar_height)
+                .set("style", "cursor: pointer;")
+                .set("fill", series_fill(idx))
+                .add(Title::new(tooltip(&puzzle, stats, phases)))
+                .add(
+                    Animate::new()
+                        .set("attributeName", "fill")
+                        .set("from", "#ec008c")
+                        .set("to", "#ec008c")
+                        .set("begin", format!("{}.mouseover", &id))
+                        .set("end", format!("{}.mouseout", &id))
+                        .set("dur", "0s"),
+                );
+            group = group.add(bar);
+
+            // Split the bar into stacked segments, each one proportional to the share of a phase
+            let phases_total = phases.total().as_secs_f32();
+            let mut segment_bottom = (margin_top + histo_height) as f32;
+            for phase in Phase::iter().filter(|_| phases_total > 0.0) {
+                let segment_height = bar_height * phases.get(phase).as_secs_f32() / phases_total;
+                segment_bottom -= segment_height;
+                let Some(shade) = phase_shade(phase) else { continue };
+                let segment = Rectangle::new()
+                    .set("x", x_position)
+                    .set("y", segment_bottom)
+                    .set("width", bar_width)
+                    .set("height", segment_height)
+                    .set("style", "pointer-events: none;")
+                    .set("fill", shade);
+                group = group.add(segment);
+            }
+
+            // Error bar, spanning one standard deviation around the mean, within the measured extremes
+            if error_bars {
+                let low = stats.mean.saturating_sub(stats.std_dev).max(stats.min);
+                let high = (stats.mean + stats.std_dev).min(stats.max);
+                let y_low = (margin_top + histo_height) as f32 - bar_height_of(low);
+                let y_high = (margin_top + histo_height) as f32 - bar_height_of(high);
+                let x_middle = x_position + bar_width / 2.0;
+
+                for (x1, y1, x2, y2) in [
+                    (x_middle, y_low, x_middle, y_high),
+                    (x_middle - bar_width / 4.0, y_low, x_middle + bar_width / 4.0, y_low),
+                    (x_middle - bar_width / 4.0, y_high, x_middle + bar_width / 4.0, y_high),
+                ] {
+                    let line = Line::new()
+                        .set("x1", x1)
+                        .set("y1", y1)
+                        .set("x2", x2)
+                        .set("y2", y2)
+                        .set("style", "pointer-events: none;")
+                        .set("stroke-width", "2")
+                        .set("stroke", "black");
+                    group = group.add(line);
+                }
+            }
+        }
+    }
 
-        group = group.add(bar);
+    if days.is_empty() {
+        let text = Text::new("No benchmark result")
+            .set("x", margin_left + histo_width / 2)
+            .set("y", margin_top + histo_height / 2)
+            .set("text-anchor", "middle")
+            .set("dominant-baseline", "middle")
+            .set("font-size", margin_bottom)
+            .set("fill", "black");
         group = group.add(text);
     }
 
     document = document.add(group);
 
     svg::save(out_path, &document).expect("Cannot save SVG file");
-    println!("Fichier SVG généré : histogram.svg");
-}
\ No newline at end of file
+    println!("Fichier SVG généré : {}", out_path);
+}
+
+/// Text of the tooltip of a bar, describing the `puzzle` and the statistics of its execution time
+fn tooltip (puzzle: &str, stats: &DurationStats, phases: &PhaseDurations) -> String {
+    format!("{}\nmean: {:?} ± {:?}\nmin: {:?}, median: {:?}, p90: {:?}, p99: {:?}, max: {:?}\n{}\nsamples: {}",
+            puzzle, stats.mean, stats.std_dev, stats.min, stats.median, stats.p90, stats.p99, stats.max,
+            phases, stats.samples)
+}
+
+/// Red cross at the bottom of a bar slot of width `width`, marking a day that failed
+fn error_marker (x: f32, bottom: f32, width: f32) -> Group {
+
+    let size = width * 0.8;
+    let (left, right) = (x + (width - size) / 2.0, x + (width + size) / 2.0);
+    let (top, bottom) = (bottom - size - width * 0.1, bottom - width * 0.1);
+
+    [(left, top, right, bottom), (left, bottom, right, top)].into_iter().fold(
+        Group::new().set("style", "cursor: pointer;"),
+        |group, (x1, y1, x2, y2)| group.add(Line::new()
+            .set("x1", x1)
+            .set("y1", y1)
+            .set("x2", x2)
+            .set("y2", y2)
+            .set("stroke-width", "4")
+            .set("stroke", "rgb(220,0,0)"))
+    )
+}
+
+/// Get the range of decades `(low, high)`, as powers of 10 in seconds, so that a logarithmic scale
+/// covers the execution times from `fastest` to `slowest` seconds, with some room below the fastest.
+fn decade_range (fastest: f64, slowest: f64) -> (i32, i32) {
+
+    let low = fastest.log10();
+    let low_decade = if low - low.floor() < 0.1 { low.floor() as i32 - 1 } else { low.floor() as i32 };
+    let high_decade = (slowest.log10().ceil() as i32).max(low_decade + 1);
+    (low_decade, high_decade)
+}
+
+/// Format the duration `10^exponent` seconds with the most suitable unit, e.g. `100 µs`
+fn decade_label (exponent: i32) -> String {
+
+    let units = [(0, "s"), (-3, "ms"), (-6, "µs"), (-9, "ns")];
+    let (unit_exponent, unit) = units.into_iter()
+        .find(|&(unit_exponent, _)| unit_exponent <= exponent)
+        .unwrap_or(units [3]);
+
+    match exponent - unit_exponent {
+        n if n >= 0 => format!("{} {}", 10u64.pow(n as u32), unit),
+        n => format!("{} {}", 10f64.powi(n), unit),
+    }
+}
+
+/// Shade applied over the bar segment of the given `phase`, if any, to tell the stacked phases apart
+fn phase_shade (phase: Phase) -> Option<&'static str> {
+    match phase {
+        Phase::Parse => Some("rgba(0, 0, 0, 0.45)"),
+        Phase::PartA => None,
+        Phase::PartB => Some("rgba(255, 255, 255, 0.35)"),
+        Phase::BothParts => Some("rgba(30, 30, 100, 0.25)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_stats () {
+        let data = (1..=10).rev().map(Duration::from_millis).collect_vec();
+
+        let stats = DurationStats::new(&data, false);
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.mean, Duration::from_micros(5500));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p90, Duration::from_millis(9));
+        assert_eq!(stats.p99, Duration::from_millis(10));
+        assert_eq!(stats.max, Duration::from_millis(10));
+        assert_eq!(stats.std_dev.as_micros(), 2872);
+
+        // The slowest and fastest measurements are excluded from the mean only
+        let stats = DurationStats::new(&data, true);
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.mean, Duration::from_micros(5500));
+        assert_eq!(stats.std_dev.as_micros(), 2291);
+        assert_eq!(stats.min, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_decade_range () {
+        assert_eq!(decade_range(0.000_150, 0.082), (-4, -1));
+        assert_eq!(decade_range(0.000_150, 5.0), (-4, 1));
+        assert_eq!(decade_range(0.001, 0.001), (-4, -3));
+        assert_eq!(decade_label(-4), "100 µs");
+        assert_eq!(decade_label(-3), "1 ms");
+        assert_eq!(decade_label(1), "10 s");
+    }
+}
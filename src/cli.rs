@@ -0,0 +1,349 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use anyhow::*;
+use crate::input::InputSource;
+use crate::verify::DEFAULT_ANSWERS_DIR;
+use crate::report::Format;
+use crate::readme::DEFAULT_README_PATH;
+use crate::baseline::{DEFAULT_BASELINES_DIR, DEFAULT_THRESHOLD};
+
+/// Default number of times each puzzle is solved during a benchmark
+const DEFAULT_REPETITIONS: usize = 100;
+
+/// Default number of times each puzzle is solved before the benchmark measurements start
+const DEFAULT_WARMUPS: usize = 1;
+
+/// Help message printed with the `help` command
+pub const USAGE: &str = "\
+Usage: adv-code-2024 <command> [year] [days] [options]
+
+Commands:
+  solve [year] [days]   Solve the puzzles and print the answers
+  bench [year] [days]   Benchmark the puzzles and print the README table rows
+  svg   [year] [days]   Benchmark the puzzles and render the histogram as a SVG file
+  list  [year] [days]   List the puzzles having a solution
+  verify [year] [days]  Solve the puzzles and compare the answers with the expected ones
+  readme [year]         Benchmark the puzzles, then rewrite their performance table and SVG in the README
+  help                  Print this message
+
+Year:
+  A year (e.g. '2024') or 'all'. Default is all the years having solutions.
+
+Days:
+  A single day (e.g. '7') or an inclusive range (e.g. '3..12' or '3-12'). Default is all days.
+
+Options:
+  -n, --repetitions <n>  Number of benchmark repetitions (default: 100)
+  -w, --warmups <n>      Number of unmeasured repetitions before the benchmark (default: 1)
+  --no-trim              Keep the 10% slowest and fastest measurements in the benchmark mean
+  -e, --error-bars       Show the standard deviation of the benchmark measurements
+  --save-baseline        Save the benchmark as a baseline, under the label given by --label
+  --label <label>        Label of the saved baseline (default: the git revision)
+  --compare <label>      Compare the benchmark with the baseline saved under <label>, or overlay it in the chart
+  --threshold <percent>  Slowdown beyond which a day is flagged as a regression (default: 10)
+  --readme <path>        Path of the README file to rewrite (default: ./README.md)
+  --baselines-dir <dir>  Store the baselines as <dir>/<label>/<yyyy>.tsv (default: ./baselines)
+  -o, --output <path>    Path of the SVG file (default: ./out/perfo-<year>.svg, or ./out/perfo-all.svg)
+  --combine              Render all the years in a single SVG chart, with their bars grouped by day
+  -i, --input <file>     Read the input of a single day from <file>, or from stdin if <file> is '-'
+  -d, --input-dir <dir>  Read the inputs from <dir>/<yyyy>/<dd>.txt (default: ./input)
+  -a, --answers-dir <dir>  Read the expected answers from <dir>/<yyyy>/<dd>.txt (default: ./answers)
+  -j, --parallel         Solve the days concurrently. The benchmark then measures the wall-clock
+                         time to solve a whole year on all the cores, instead of each day on a single thread
+  -f, --format <format>  Output format of 'solve' and 'bench': text, json or csv (default: text)
+  -p, --param <name>=<value>  Override a parameter of the puzzle, e.g. to solve the example of the statement.
+                         The parameters are listed by the 'list' command
+  --save                 When verifying, save the answers that are not known yet as the expected ones
+";
+
+/// What the application is asked to do
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Solve,
+    Bench,
+    Svg,
+    List,
+    Verify,
+    Readme,
+    Help,
+}
+
+/// The command line arguments, once parsed
+#[derive(Debug, Clone)]
+pub struct Args {
+    pub mode: Mode,
+    pub year: Option<u32>,
+    pub days: RangeInclusive<u32>,
+    pub repetitions: usize,
+    pub warmups: usize,
+    pub trim_outliers: bool,
+    pub error_bars: bool,
+    pub save_baseline: bool,
+    pub label: Option<String>,
+    pub compare: Option<String>,
+    pub threshold: f64,
+    pub baselines_dir: PathBuf,
+    pub readme: PathBuf,
+    pub output: Option<String>,
+    pub combine: bool,
+    pub input: InputSource,
+    pub answers_dir: PathBuf,
+    pub save_answers: bool,
+    pub format: Format,
+    pub parallel: bool,
+
+    /// Overridden puzzle parameters, as name and value
+    pub params: Vec<(String, String)>,
+}
+
+impl Args {
+
+    /// Parse the command line arguments `args` (without the program name)
+    pub fn parse (args: impl Iterator<Item = String>) -> Result<Args> {
+
+        let mut args = args.peekable();
+
+        let mode = match args.next().as_deref() {
+            Some("solve") => Mode::Solve,
+            Some("bench") => Mode::Bench,
+            Some("svg")   => Mode::Svg,
+            Some("list")  => Mode::List,
+            Some("verify") => Mode::Verify,
+            Some("readme") => Mode::Readme,
+            Some("help") | Some("-h") | Some("--help") | None => Mode::Help,
+            Some(other) => bail!("Unknown command '{}'\n\n{}", other, USAGE),
+        };
+
+        let mut parsed = Args {
+            mode,
+            year: None,
+            days: 1..=25,
+            repetitions: DEFAULT_REPETITIONS,
+            warmups: DEFAULT_WARMUPS,
+            trim_outliers: true,
+            error_bars: false,
+            save_baseline: false,
+            label: None,
+            compare: None,
+            threshold: DEFAULT_THRESHOLD,
+            baselines_dir: PathBuf::from(DEFAULT_BASELINES_DIR),
+            readme: PathBuf::from(DEFAULT_README_PATH),
+            output: None,
+            combine: false,
+            input: InputSource::default(),
+            answers_dir: PathBuf::from(DEFAULT_ANSWERS_DIR),
+            save_answers: false,
+            format: Format::Text,
+            parallel: false,
+            params: vec![],
+        };
+        if mode == Mode::Help { return Ok(parsed) }
+
+        // Positional arguments: optionally the year, then optionally the days
+        if let Some(year) = args.next_if(|arg| !arg.starts_with('-')) {
+            parsed.year = match year.as_str() {
+                "all" => None,
+                _ => Some(year.parse().map_err(|_| anyhow!("Invalid year '{}'", year))?),
+            };
+
+            if let Some(days) = args.next_if(|arg| !arg.starts_with('-')) {
+                parsed.days = parse_days(&days)?;
+            }
+        }
+
+        // Options
+        while let Some(option) = args.next() {
+            let mut value = || args.next().ok_or(anyhow!("Missing value for option '{}'", option));
+            match option.as_str() {
+                "-n" | "--repetitions" => {
+                    let value = value()?;
+                    parsed.repetitions = value.parse().map_err(|_| anyhow!("Invalid repetitions '{}'", value))?;
+                    if parsed.repetitions == 0 { bail!("The number of repetitions must be positive") }
+                },
+                "-w" | "--warmups" => {
+                    let value = value()?;
+                    parsed.warmups = value.parse().map_err(|_| anyhow!("Invalid warmups '{}'", value))?;
+                },
+                "--no-trim" => parsed.trim_outliers = false,
+                "-e" | "--error-bars" => parsed.error_bars = true,
+                "--save-baseline" => parsed.save_baseline = true,
+                "--label" => parsed.label = Some(value()?),
+                "--compare" => parsed.compare = Some(value()?),
+                "--threshold" => {
+                    let value = value()?;
+                    parsed.threshold = value.parse().ok().filter(|t: &f64| *t >= 0.0)
+                        .ok_or(anyhow!("Invalid threshold '{}'", value))?;
+                },
+                "--readme" => parsed.readme = PathBuf::from(value()?),
+                "--baselines-dir" => parsed.baselines_dir = PathBuf::from(value()?),
+                "-o" | "--output" => parsed.output = Some(value()?),
+                "--combine" => parsed.combine = true,
+                "-i" | "--input" => parsed.input = match value()?.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::File(PathBuf::from(path)),
+                },
+                "-d" | "--input-dir" => parsed.input = InputSource::Directory(PathBuf::from(value()?)),
+                "-a" | "--answers-dir" => parsed.answers_dir = PathBuf::from(value()?),
+                "--save" => parsed.save_answers = true,
+                "-j" | "--parallel" => parsed.parallel = true,
+                "-p" | "--param" => {
+                    let value = value()?;
+                    let (name, param) = value.split_once('=')
+                        .ok_or(anyhow!("Invalid parameter '{}', expecting <name>=<value>", value))?;
+                    parsed.params.push((name.trim().to_string(), param.trim().to_string()));
+                },
+                "-f" | "--format" => {
+                    let value = value()?;
+                    parsed.format = Format::from_name(&value).ok_or(anyhow!("Unknown format '{}'", value))?;
+                },
+                _ => bail!("Unknown option '{}'\n\n{}", option, USAGE),
+            }
+        }
+
+        // The machine-readable formats only apply to the results of solving and benchmarking
+        if parsed.format != Format::Text && !matches!(parsed.mode, Mode::Solve | Mode::Bench) {
+            bail!("The output format can only be selected with the 'solve' and 'bench' commands");
+        }
+
+        // The parallel benchmark measures whole years, not days
+        if parsed.parallel {
+            if !matches!(parsed.mode, Mode::Solve | Mode::Bench) {
+                bail!("Only the 'solve' and 'bench' commands can run in parallel");
+            }
+            if parsed.mode == Mode::Bench && (parsed.format != Format::Text || parsed.save_baseline || parsed.compare.is_some()) {
+                bail!("The parallel benchmark only supports the text format, without baselines");
+            }
+        }
+
+        // The baselines are made of benchmark results, and the comparison is printed as text or charted
+        if parsed.save_baseline && parsed.mode != Mode::Bench {
+            bail!("The baselines can only be saved with the 'bench' command");
+        }
+        if parsed.compare.is_some() && !matches!(parsed.mode, Mode::Bench | Mode::Svg) {
+            bail!("The baselines can only be compared with the 'bench' and 'svg' commands");
+        }
+        if parsed.combine && parsed.mode != Mode::Svg {
+            bail!("Only the 'svg' command can combine several years");
+        }
+        if parsed.compare.is_some() && parsed.format != Format::Text {
+            bail!("The comparison with a baseline is only available with the text format");
+        }
+
+        // The README tables list all the days of a year
+        if parsed.mode == Mode::Readme && (parsed.days != (1..=25) || parsed.input.is_single()) {
+            bail!("The README tables can only be made from all the days, with their inputs read from a directory");
+        }
+
+        // Each README table links to the SVG of its own year
+        if parsed.mode == Mode::Readme && parsed.output.is_some() && parsed.year.is_none() {
+            bail!("An output path for the README requires to select a single year");
+        }
+
+        // A single SVG file cannot receive the results of several years, unless they are combined
+        if parsed.output.is_some() && parsed.year.is_none() && !parsed.combine {
+            bail!("An output path requires to select a single year, or to combine them");
+        }
+
+        // The parameters are specific to a puzzle, and change its answers
+        if !parsed.params.is_empty() {
+            if !matches!(parsed.mode, Mode::Solve | Mode::Bench) {
+                bail!("The puzzle parameters can only be overridden with the 'solve' and 'bench' commands");
+            }
+            if parsed.year.is_none() || parsed.days.start() != parsed.days.end() {
+                bail!("Overriding the puzzle parameters requires to select a single year and day");
+            }
+        }
+
+        // A single input file (or stdin) cannot feed several days
+        if parsed.input.is_single() && (parsed.year.is_none() || parsed.days.start() != parsed.days.end()) {
+            bail!("A single input file requires to select a single year and day");
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Parse a single day (`7`) or an inclusive range of days (`3..12` or `3-12`)
+fn parse_days (days: &str) -> Result<RangeInclusive<u32>> {
+
+    let parse_day = |day: &str| -> Result<u32> {
+        match day.trim().parse::<u32>() {
+            std::result::Result::Ok(day) if (1..=25).contains(&day) => Ok(day),
+            _ => bail!("Invalid day '{}', expecting a number between 1 and 25", day),
+        }
+    };
+
+    let range = match days.split_once("..").or(days.split_once('-')) {
+        Some((from, to)) => parse_day(from)?..=parse_day(to)?,
+        None => { let day = parse_day(days)?; day..=day },
+    };
+
+    if range.is_empty() { bail!("Invalid range of days '{}'", days) }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse the command line `line`
+    fn parse (line: &str) -> Result<Args> {
+        Args::parse(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_days () {
+        assert_eq!(parse_days("7").unwrap(), 7..=7);
+        assert_eq!(parse_days("3..12").unwrap(), 3..=12);
+        assert_eq!(parse_days("3-12").unwrap(), 3..=12);
+        assert!(parse_days("12..3").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn test_parse () {
+        let args = parse("bench 2022 5 -n 10").unwrap();
+        assert_eq!(args.mode, Mode::Bench);
+        assert_eq!(args.year, Some(2022));
+        assert_eq!(args.days, 5..=5);
+        assert_eq!(args.repetitions, 10);
+        assert_eq!(args.warmups, DEFAULT_WARMUPS);
+
+        let args = parse("bench 2024 1 -i - -w 0").unwrap();
+        assert_eq!(args.input, InputSource::Stdin);
+        assert_eq!(args.warmups, 0);
+        assert!(args.trim_outliers);
+
+        let args = parse("svg 2024 --no-trim -e").unwrap();
+        assert!(!args.trim_outliers);
+        assert!(args.error_bars);
+
+        let args = parse("solve").unwrap();
+        assert_eq!(args.year, None);
+        assert_eq!(args.days, 1..=25);
+
+        assert!(parse("solve 2024 -i input.txt").is_err());
+        assert!(parse("solve 2024 1 -i input.txt").is_ok());
+        assert!(parse("list -f json").is_err());
+
+        let args = parse("bench 2024 --compare v1 --threshold 5").unwrap();
+        assert_eq!(args.compare.as_deref(), Some("v1"));
+        assert_eq!(args.threshold, 5.0);
+        assert!(parse("solve --save-baseline").is_err());
+
+        assert!(parse("svg -o all.svg").is_err());
+        assert!(parse("svg -o all.svg --combine").is_ok());
+        assert!(parse("readme all -o x.svg").is_err());
+        assert!(parse("readme 2024 -o x.svg").is_ok());
+
+        assert!(parse("solve 2024 -j").unwrap().parallel);
+        assert!(parse("bench 2024 -j -f json").is_err());
+        assert!(parse("verify -j").is_err());
+
+        let args = parse("solve 2024 14 -i example.txt -p width=11 --param height=7").unwrap();
+        assert_eq!(args.params, [("width".to_string(), "11".to_string()), ("height".to_string(), "7".to_string())]);
+        assert!(parse("solve 2024 14 -p width").is_err());
+        assert!(parse("solve 2024 -p width=11").is_err());
+        assert!(parse("verify 2024 14 -p width=11").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fs::File;
+use std::io::{stdin, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use anyhow::*;
+
+/// Default directory where the puzzle input files are looked for
+const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Where the puzzle input files are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+
+    /// A directory organized as `<dir>/<yyyy>/<dd>.txt`
+    Directory (PathBuf),
+
+    /// A single file, used whatever the year and day
+    File (PathBuf),
+
+    /// The standard input
+    Stdin,
+}
+
+impl Default for InputSource {
+    fn default() -> Self { InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR)) }
+}
+
+impl InputSource {
+
+    /// Return `true` if this source provides the input of a single puzzle only
+    pub fn is_single (&self) -> bool {
+        !matches!(self, InputSource::Directory(_))
+    }
+
+    /// Load the input of the given `day` of the `year`, as a vector of strings
+    pub fn load (&self, year: u32, day: u32) -> Result<Vec<String>> {
+
+        match self {
+            InputSource::Directory(dir) => {
+                let path = dir.join(format!("{}", year)).join(format!("{:02}.txt", day));
+                read_file(&path)
+            },
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => stdin().lock().lines().collect::<std::io::Result<_>>()
+                .map_err(|err| anyhow!("Failed to read the standard input: {}", err)),
+        }
+    }
+}
+
+/// Read the file at `path`, as a vector of strings
+fn read_file (path: &Path) -> Result<Vec<String>> {
+
+    let file = File::open(path)
+        .map_err(|err| anyhow!("Failed to open input file {}: {}", path.display(), err))?;
+
+    BufReader::new(file).lines().collect::<std::io::Result<_>>()
+        .map_err(|err| anyhow!("Failed to read input file {}: {}", path.display(), err))
+}
@@ -3,19 +3,36 @@ mod y2022;
 mod y2023;
 mod y2024;
 mod benchmark;
+mod cli;
+mod input;
+mod verify;
+mod report;
+mod timing;
+mod baseline;
+mod readme;
+mod params;
 
 use crate::y2022::Y2022;
 use crate::y2024::Y2024;
 use anyhow::*;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::ops::{RangeBounds, Shl};
 use std::result::Result::Ok;
+use std::collections::HashMap;
 use std::time::Duration;
+use std::path::Path;
 use itertools::Itertools;
-use crate::benchmark::{benchmark_year, make_svg, BenchmarkResult};
+use rayon::prelude::*;
+use crate::benchmark::{benchmark_year, benchmark_year_parallel, make_svg, YearBenchmark, BenchmarkOptions, BenchmarkResult, DayBenchmark, Series};
 use crate::y2023::Y2023;
+use crate::cli::{Args, Mode, USAGE};
+use crate::input::InputSource;
+use crate::verify::{verify_year, VerifySummary};
+use crate::report::{benchmark_records, solve_records, Format};
+use crate::timing::{Phase, PhaseDurations, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::readme::{has_section, performance_table, read_readme, relative_link, update_section, write_readme};
+use crate::baseline::{compare, default_label, load_baseline, print_comparisons, save_baseline, Trend};
 
 pub use tools::{Cell, GridCell};
 use crate::tools::BitSet;
@@ -23,12 +40,18 @@ use crate::tools::BitSet;
 /// https://www.maurits.vdschee.nl/scatterplot/
 
 /// A function solving the problem of the day.
-/// * Input param is a vector of strings (input file)
+/// * Input params are a vector of strings (input file), the values of the puzzle [parameters](Params),
+///   and a timer to report the end of each [Phase]
 /// * Output are the two problem answers (part a and b)
-type FnDay = fn(&[&str]) -> Result <(Solution, Solution)>;
+type FnDay = fn(&[&str], &Params, &mut PhaseTimer) -> Result <(Solution, Solution)>;
+
+/// Outcome of solving the puzzles of some year: the two answers and the time spent in
+/// each phase to compute them, or the error, indexed on the day numbers.
+type SolveResult = HashMap<u32, Result<(Solution, Solution, PhaseDurations)>>;
 
 /// A module containing all the functions to solve the daily problems of some year.
-trait Year {
+/// It can be shared between threads, to solve several days concurrently.
+trait Year : Sync {
     fn get_year (&self) -> u32;
 
     /// Get the function solving the problem of the given `day`
@@ -36,11 +59,28 @@ trait Year {
 
     /// Get the puzzle name for the given `day`
     fn get_day_name (&self, day: u32) -> Option<&str>;
+
+    /// Get the tunable parameters of the given `day`, along with their defaults for the real puzzle input
+    fn get_day_params (&self, _day: u32) -> &'static [ParamSpec] { &[] }
+}
+
+/// All the years with solved puzzles, in chronological order
+const YEARS: &[&dyn Year] = &[&Y2022, &Y2023, &Y2024];
+
+/// Find the module of the given `year` in the [registry](YEARS)
+fn find_year (year: u32) -> Option<&'static dyn Year> {
+    YEARS.iter().find(|y| y.get_year() == year).copied()
 }
 
-/// Each problem expects a final numerical or textual solution
+/// Each problem expects a final numerical or textual solution.
+/// Wide integers are available for the puzzles whose answers do not fit in 64 bits.
 enum Solution {
     Unsigned (usize),
+    Signed (isize),
+    #[allow(dead_code)]
+    BigUnsigned (u128),
+    #[allow(dead_code)]
+    BigSigned (i128),
     Text (String),
 }
 
@@ -48,6 +88,9 @@ impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Solution::Unsigned (n) => n.fmt(f),
+            Solution::Signed (n) => n.fmt(f),
+            Solution::BigUnsigned (n) => n.fmt(f),
+            Solution::BigSigned (n) => n.fmt(f),
             Solution::Text(s) => s.fmt(f),
         }
     }
@@ -56,85 +99,279 @@ impl Display for Solution {
 
 fn main() -> Result<()> {
 
-    solve_year(Y2022, 23..24);
-    solve_year(Y2023, 10..10);
-    solve_year(Y2024, 23..23);
+    let args = Args::parse(std::env::args().skip(1))?;
+    if args.mode == Mode::Help {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+
+    // Either the requested year, or all of them
+    let years: Vec<&dyn Year> = match args.year {
+        Some(year) => vec![find_year(year).ok_or(anyhow!("No solutions for year {}", year))?],
+        None => YEARS.to_vec(),
+    };
+
+    // The README to rewrite is checked before any benchmark: the years without a performance table are skipped
+    let mut readme = None;
+    let years = match args.mode {
+        Mode::Readme => {
+            let content = read_readme(&args.readme)?;
+            let (years, skipped): (Vec<_>, Vec<_>) = years.into_iter()
+                .partition(|year| has_section(&content, year.get_year()));
+            for year in skipped {
+                eprintln!("Warning: no performance table for year {} in {}, skipped", year.get_year(), args.readme.display());
+            }
+            if years.is_empty() { bail!("No performance table to update in {}", args.readme.display()) }
+            readme = Some(content);
+            years
+        },
+        _ => years,
+    };
+
+    let options = BenchmarkOptions {
+        num_warmups: args.warmups,
+        num_repetitions: args.repetitions,
+        trim_outliers: args.trim_outliers,
+    };
+
+    // Label of the baseline to save, if any
+    let label = match (&args.label, args.save_baseline) {
+        (Some(label), _) => label.clone(),
+        (None, true) => default_label()?,
+        (None, false) => String::new(),
+    };
 
-    let result = benchmark_year(&Y2022, 100);
-    print_benchmark_result(Y2022, &result);
-    make_svg(&result, "./out/perfo-2022.svg");
+    let mut summary = VerifySummary::default();
+    let mut regressions = 0;
+    let mut records = vec![];
+    let mut combined_series = vec![];
+    for year in years {
+        match args.mode {
+            Mode::Solve => {
+                let result = solve_year(year, args.days.clone(), &args.params, &args.input, args.parallel);
+                match args.format {
+                    Format::Text => print_solve_result(year, &result),
+                    _ => records.extend(solve_records(year, &result)),
+                }
+            },
+            Mode::Bench if args.parallel => {
+                let result = benchmark_year_parallel(year, args.days.clone(), &options, &args.params, &args.input);
+                print_year_benchmark(year, &result);
+            },
+            Mode::Bench => {
+                let result = benchmark_year(year, args.days.clone(), &options, &args.params, &args.input);
+                match args.format {
+                    Format::Text => print_benchmark_result(year, &result, args.error_bars),
+                    _ => records.extend(benchmark_records(year, &result)),
+                }
+
+                if args.save_baseline {
+                    let path = save_baseline(year, &result, &args.baselines_dir, &label)?;
+                    eprintln!("Baseline saved to {}", path.display());
+                }
+
+                if let Some(baseline_label) = &args.compare {
+                    let baseline = load_baseline(year, &args.baselines_dir, baseline_label)?;
+                    let comparisons = compare(&result, &baseline, args.threshold);
+                    print_comparisons(year, baseline_label, &comparisons);
+                    regressions += comparisons.iter().filter(|c| c.trend == Trend::Regressed).count();
+                }
+            },
+            Mode::Svg => {
+                let result = benchmark_year(year, args.days.clone(), &options, &args.params, &args.input);
+                let mut series = vec![Series { label: year.get_year().to_string(), year, result }];
+
+                // Overlay the baseline to compare with, if any
+                if let Some(baseline_label) = &args.compare {
+                    let result = load_baseline(year, &args.baselines_dir, baseline_label)?;
+                    series.push(Series { label: format!("{} ({})", year.get_year(), baseline_label), year, result });
+                }
+
+                if args.combine { combined_series.extend(series); }
+                else {
+                    let default_path = format!("./out/perfo-{}.svg", year.get_year());
+                    make_svg(&series, args.output.as_deref().unwrap_or(&default_path), args.error_bars);
+                }
+            },
+            Mode::Readme => {
+                let result = benchmark_year(year, args.days.clone(), &options, &args.params, &args.input);
+                let series = [Series { label: year.get_year().to_string(), year, result }];
+                let default_path = format!("./out/perfo-{}.svg", year.get_year());
+                let svg_path = args.output.clone().unwrap_or(default_path);
+                make_svg(&series, &svg_path, args.error_bars);
+
+                let svg_link = relative_link(&args.readme, Path::new(&svg_path))?;
+                let content = readme.as_deref().expect("README loaded for this mode");
+                readme = Some(update_section(content, year, &series [0].result, &svg_link)?);
+            },
+            Mode::List => list_days(year, args.days.clone()),
+            Mode::Verify => {
+                let year_summary = verify_year(
+                    year, args.days.clone(), &args.input, &args.answers_dir, args.save_answers
+                );
+                summary.merge(&year_summary);
+            },
+            Mode::Help => unreachable!(),
+        }
+    }
+
+    match args.format {
+        Format::Text => {},
+        Format::Json => print!("{}", report::to_json(&records)),
+        Format::Csv => print!("{}", report::to_csv(&records)),
+    }
+
+    // The README is written once, all its tables being updated
+    if let Some (readme) = &readme {
+        write_readme(&args.readme, readme)?;
+        eprintln!("Performance tables updated in {}", args.readme.display());
+    }
+
+    if args.combine {
+        make_svg(&combined_series, args.output.as_deref().unwrap_or("./out/perfo-all.svg"), args.error_bars);
+    }
+
+    if regressions > 0 {
+        bail!("{} day(s) regressed by more than {}%", regressions, args.threshold);
+    }
+
+    if args.mode == Mode::Verify {
+        println!("\n{} passed, {} failed, {} missing", summary.passed, summary.failed, summary.missing);
+        if summary.failed > 0 { bail!("{} answer(s) failed the verification", summary.failed) }
+    }
 
     Ok(())
 }
 
-fn print_benchmark_result<Y> (year: Y, benchmark_result: &BenchmarkResult)
-where Y : Year {
+/// Print the days of the provided `year` module that have a solution, with their puzzle name
+/// and the default values of their parameters.
+fn list_days<Y> (year: &Y, day_range: impl RangeBounds<u32>)
+where Y : Year + ?Sized {
+
+    println!("Year {}:", year.get_year());
+    for day in (1..= 25).filter(|day| day_range.contains(day)) {
+        if year.get_day_fn(day).is_none() { continue }
+        println!(" {:02} - {}", day, year.get_day_name(day).unwrap_or_default());
+        for spec in year.get_day_params(day) {
+            println!("      -p {:<20} {}", format!("{}={}", spec.name, spec.default), spec.help);
+        }
+    }
+}
+
+/// Print the benchmark `benchmark_result` of the provided `year` module: first the README
+/// performance table, with the standard deviation if `error_bars` is set, then the detailed statistics.
+fn print_benchmark_result<Y> (year: &Y, benchmark_result: &BenchmarkResult, error_bars: bool)
+where Y : Year + ?Sized {
+
+    for row in performance_table(year, benchmark_result, error_bars) {
+        println!("{}", row);
+    }
 
-    let web = format! ("https://adventofcode.com/{}/day/", year.get_year());
-    let source = format! ("./src/y{}/day_", year.get_year());
+    let ms = |duration: Duration| format!("{:.1$}", duration.as_micros() as f64 / 1000.0, 3);
 
+    // Then the statistics of each day, and the split of the execution time between the phases, in ms
+    let headers = ["samples", "min", "median", "p90", "p99", "std dev"].into_iter()
+        .chain(Phase::iter().map(|phase| phase.name()))
+        .collect_vec();
+    println!();
+    println!("| Day | {} |", headers.iter().map(|header| format!("{:>10}", header)).join(" | "));
+    println!("|-----|{}", headers.iter().map(|_| "------------|").join(""));
     for key in benchmark_result.keys().sorted() {
-        if let Some(Ok(duration)) = benchmark_result.get(key) {
+        if let Some(Ok(DayBenchmark { stats, phases, .. })) = benchmark_result.get(key) {
 
-            let puzzle_name = year.get_day_name(*key).unwrap();
-            let formatted = format!("{:.1$}", duration.as_micros() as f64 / 1000.0, 3);
-            println!("| {:02}  | [{}]({}{})      | [day_{:02}.rs]({}{:02}.rs) | {}      |",
-                key, puzzle_name, web, key, key, source, key, formatted);
+            // The phases the day does not time separately are left empty, rather than shown as 0
+            let values = [stats.min, stats.median, stats.p90, stats.p99, stats.std_dev].into_iter().map(Some)
+                .chain(Phase::iter().map(|phase| phases.get_timed(phase)))
+                .map(|duration| format!("{:>10}", duration.map(ms).unwrap_or_default()))
+                .join(" | ");
+            println!("| {:02}  | {:>10} | {} |", key, stats.samples, values);
         }
     }
 }
 
-/// Solve for all the days of the provided `year` module.
-fn solve_year<Y> (year: Y, day_range: impl RangeBounds<u32>)
-where Y : Year {
+/// Print the wall-clock benchmark `year_benchmark` of the provided `year` module, whose days were solved concurrently
+fn print_year_benchmark<Y> (year: &Y, year_benchmark: &YearBenchmark)
+where Y : Year + ?Sized {
+
+    let ms = |duration: Duration| format!("{:.1$}", duration.as_micros() as f64 / 1000.0, 3);
+    let stats = &year_benchmark.stats;
+
+    println!("Year {}, {} days solved in parallel on {} thread(s) (wall-clock time, NOT single-thread):",
+             year.get_year(), year_benchmark.num_days, year_benchmark.num_threads);
+    println!(" mean: {} ms ± {} ms, min: {} ms, median: {} ms, p90: {} ms, max: {} ms, samples: {}",
+             ms(stats.mean), ms(stats.std_dev), ms(stats.min), ms(stats.median), ms(stats.p90), ms(stats.max), stats.samples);
+    for (day, err) in year_benchmark.failed_days.iter() {
+        println!(" day {} excluded, in ERROR: {}", day, err);
+    }
+}
+
+/// Solve for all the days of the provided `year` module, reading the puzzle inputs from `input`.
+/// The default values of the puzzle parameters are replaced by the `overrides` (name and value).
+/// When `parallel` is set, the days are solved concurrently, on all the available cores.
+fn solve_year<Y> (year: &Y, day_range: impl RangeBounds<u32>, overrides: &[(String, String)], input: &InputSource, parallel: bool) -> SolveResult
+where Y : Year + ?Sized {
+
+    // Get the functions related to the selected days, skipping the days without any
+    let days = (1..= 25).filter(|day| day_range.contains(day))
+        .filter_map(|day| Some((day, year.get_day_fn(day)?)))
+        .collect_vec();
+
+    let solve = |&(day, fn_solve): &(u32, FnDay)| {
+        let result = Params::with_overrides(year.get_day_params(day), overrides)
+            .and_then(|params| solve_day(year.get_year(), day, fn_solve, &params, input));
+        (day, result)
+    };
+    match parallel {
+        true => days.par_iter().map(solve).collect(),
+        false => days.iter().map(solve).collect(),
+    }
+}
+
+/// Print the solutions `solve_result` found for the provided `year` module.
+fn print_solve_result<Y> (year: &Y, solve_result: &SolveResult)
+where Y : Year + ?Sized {
 
     println!("=========================");
     println!("Solutions for year {:?}", year.get_year());
     println!("WARNING: execution time may be noisy!");
 
-    for day in (1..= 25).filter(|day| day_range.contains(day)) {
-
-        // Get the function related to the current day, or skip the test
-        let Some (fn_solve) = year.get_day_fn(day) else { continue };
-        match solve_day(year.get_year(), day, fn_solve) {
+    for day in solve_result.keys().sorted() {
+        match &solve_result[day] {
 
-            Ok((a, b, duration)) => {
-                println!("\n| day {}, in {:?}", day, duration);
+            Ok((a, b, durations)) => {
+                println!("\n| day {}, in {:?} ({})", day, durations.total(), durations);
                 println!(" - Part A: {}", a);
                 println!(" - Part B: {}", b);
             }
             Err(err) => {
                 println!("\n| day {}, in ERROR", day);
-                println!(" * {}", err.to_string());
+                println!(" * {}", err);
             }
         };
     }
 }
 
 
-/// Solve for the given `day` of the `year`, thanks to the provided function `fn_solve`.
-/// In case of success, return the two answers and the duration to compute them.
-/// The corresponding input file is expected to be found at the location `input/<yyyy>/<dd>.txt`
-fn solve_day (year: u32, day: u32, fn_solve: FnDay) -> Result <(Solution, Solution, Duration)> {
+/// Solve for the given `day` of the `year`, thanks to the provided function `fn_solve` and the puzzle `params`.
+/// In case of success, return the two answers and the time spent in each phase to compute them.
+/// The puzzle input is loaded from `input` (by default, at the location `input/<yyyy>/<dd>.txt`)
+fn solve_day (year: u32, day: u32, fn_solve: FnDay, params: &Params, input: &InputSource) -> Result <(Solution, Solution, PhaseDurations)> {
 
-    // Extract the input file as a vector of strings
-    let input_file = format! ("input/{}/{:02}.txt", year, day);
-    let br = BufReader::new(File::open(&input_file)?);
-    let content: Result<Vec<String>, std::io::Error> = br.lines().collect();
+    // Extract the input as a vector of strings
+    let lines = input.load(year, day)?;
+    solve_lines(fn_solve, params, &lines)
+}
 
-    // Measure time ...
-    let start = std::time::Instant::now();
-    let result = match content {
+/// Solve a puzzle whose input `lines` are already loaded, thanks to the provided function `fn_solve` and the puzzle `params`.
+/// In case of success, return the two answers and the time spent in each phase to compute them.
+fn solve_lines (fn_solve: FnDay, params: &Params, lines: &[String]) -> Result <(Solution, Solution, PhaseDurations)> {
 
-        Ok(lines) => {
-            // ... to solve
-            let line_refs: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
-            fn_solve (&line_refs)?
-        }
-        Err(err) => { bail!("Failed to read input file: {}", err.to_string()); }
-    };
-    let duration = start.elapsed();
+    let line_refs: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+
+    // Measure time to solve, phase by phase
+    let mut timer = PhaseTimer::start();
+    let result = fn_solve (&line_refs, params, &mut timer)?;
 
-    // Return the two answers and the duration
-    Ok((result.0, result.1, duration))
+    // Return the two answers and the durations
+    Ok((result.0, result.1, timer.durations()))
 }
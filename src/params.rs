@@ -0,0 +1,95 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use anyhow::*;
+use itertools::Itertools;
+
+/// Declaration of a tunable parameter of a puzzle, e.g. the size of an area given in its statement
+#[derive(Debug, Copy, Clone)]
+pub struct ParamSpec {
+    pub name: &'static str,
+
+    /// Value used to solve the real puzzle input
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl ParamSpec {
+    pub const fn new (name: &'static str, default: &'static str, help: &'static str) -> ParamSpec {
+        ParamSpec { name, default, help }
+    }
+}
+
+/// Values of the parameters of a puzzle: the defaults of its [specifications](ParamSpec),
+/// possibly overridden, e.g. to solve the small examples of the statement.
+#[derive(Debug, Clone)]
+pub struct Params {
+    specs: &'static [ParamSpec],
+    values: Vec<String>,
+}
+
+impl Params {
+
+    /// Make the parameters declared by the `specs`, set to their default values
+    pub fn new (specs: &'static [ParamSpec]) -> Params {
+        Params {
+            specs,
+            values: specs.iter().map(|spec| spec.default.to_string()).collect(),
+        }
+    }
+
+    /// Make the parameters declared by the `specs`, with the values of the `overrides` (name and value)
+    pub fn with_overrides (specs: &'static [ParamSpec], overrides: &[(String, String)]) -> Result<Params> {
+        overrides.iter().try_fold(Params::new(specs), |params, (name, value)| params.with(name, value))
+    }
+
+    /// Override the value of the parameter `name`
+    pub fn with (mut self, name: &str, value: impl Display) -> Result<Params> {
+        let idx = self.index(name)?;
+        self.values [idx] = value.to_string();
+        Ok(self)
+    }
+
+    /// Get the value of the parameter `name`, converted to the type `T`
+    pub fn get<T> (&self, name: &str) -> Result<T>
+    where T: FromStr, T::Err: Display {
+        let value = &self.values [self.index(name)?];
+        value.parse().map_err(|err| anyhow!("Invalid value '{}' for parameter '{}': {}", value, name, err))
+    }
+
+    /// Position of the parameter `name` among the specifications
+    fn index (&self, name: &str) -> Result<usize> {
+        self.specs.iter().position(|spec| spec.name == name).ok_or_else(|| match self.specs.is_empty() {
+            true => anyhow!("Unknown parameter '{}', this puzzle has none", name),
+            false => anyhow!("Unknown parameter '{}', expecting one of: {}", name,
+                             self.specs.iter().map(|spec| spec.name).join(", ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECS: &[ParamSpec] = &[
+        ParamSpec::new("width", "101", "Width of the area"),
+        ParamSpec::new("label", "abc", "Some text"),
+    ];
+
+    #[test]
+    fn test_params () {
+        let params = Params::new(SPECS);
+        assert_eq!(params.get::<usize>("width").unwrap(), 101);
+        assert_eq!(params.get::<String>("label").unwrap(), "abc");
+        assert!(params.get::<usize>("label").is_err());
+        assert!(params.get::<usize>("height").is_err());
+
+        let params = params.with("width", 11).unwrap();
+        assert_eq!(params.get::<u8>("width").unwrap(), 11);
+        assert!(params.with("height", 7).is_err());
+
+        let overrides = [("label".to_string(), "xyz".to_string())];
+        let params = Params::with_overrides(SPECS, &overrides).unwrap();
+        assert_eq!(params.get::<String>("label").unwrap(), "xyz");
+        assert_eq!(params.get::<usize>("width").unwrap(), 101);
+    }
+}
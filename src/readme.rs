@@ -0,0 +1,184 @@
+use std::fs;
+use std::path::{Component, Path};
+use std::time::Duration;
+use anyhow::*;
+use std::result::Result::Ok;
+use itertools::Itertools;
+use crate::Year;
+use crate::benchmark::{BenchmarkResult, DayBenchmark};
+
+/// Default location of the README file whose performance tables are regenerated
+pub const DEFAULT_README_PATH: &str = "README.md";
+
+/// Make the markdown performance table of the benchmark `benchmark_result` of the `year`,
+/// with the standard deviations if `error_bars` is set. The failed days are left out.
+pub fn performance_table<Y> (year: &Y, benchmark_result: &BenchmarkResult, error_bars: bool) -> Vec<String>
+where Y : Year + ?Sized {
+
+    let web = format! ("https://adventofcode.com/{}/day/", year.get_year());
+    let source = format! ("./src/y{}/day_", year.get_year());
+
+    let cells = benchmark_result.keys().sorted().filter_map(|&day| {
+        let Ok(DayBenchmark { stats, .. }) = &benchmark_result[&day] else { return None };
+
+        let puzzle_name = year.get_day_name(day).unwrap_or_default();
+        let perfo = match error_bars {
+            true => format!("{} ± {}", format_ms(stats.mean), format_ms(stats.std_dev)),
+            false => format_ms(stats.mean),
+        };
+        Some([
+            format!("{:02}", day),
+            format!("[{}]({}{})", puzzle_name, web, day),
+            format!("[day_{:02}.rs]({}{:02}.rs)", day, source, day),
+            perfo,
+        ])
+    }).collect_vec();
+
+    // Align the columns on the widest cells
+    let header = ["Day", "Puzzle", "Code", "Perf. (ms)"];
+    let widths = (0..header.len()).map(|col| {
+        cells.iter().map(|row| row [col].chars().count()).chain([header [col].len()]).max().unwrap_or_default()
+    }).collect_vec();
+
+    let format_row = |row: &[String]| {
+        let cells = row.iter().zip(&widths).map(|(cell, &width)| format!(" {:<1$} ", cell, width)).join("|");
+        format!("|{}|", cells)
+    };
+
+    let separator = format!("|{}|", widths.iter().map(|&width| "-".repeat(width + 2)).join("|"));
+    let header = header.map(String::from);
+
+    [format_row(&header), separator].into_iter()
+        .chain(cells.iter().map(|row| format_row(row)))
+        .collect()
+}
+
+/// Format a `duration` in ms, with 4 significant digits
+fn format_ms (duration: Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    match ms {
+        ms if ms >= 1000.0 => format!("{:.0}", ms),
+        ms if ms >= 100.0 => format!("{:.1}", ms),
+        ms if ms >= 10.0 => format!("{:.2}", ms),
+        ms => format!("{:.3}", ms),
+    }
+}
+
+/// Read the README file at `readme_path`
+pub fn read_readme (readme_path: &Path) -> Result<String> {
+    fs::read_to_string(readme_path).map_err(|err| anyhow!("Failed to read {}: {}", readme_path.display(), err))
+}
+
+/// Write the `readme` content into the README file at `readme_path`
+pub fn write_readme (readme_path: &Path, readme: &str) -> Result<()> {
+    fs::write(readme_path, readme).map_err(|err| anyhow!("Failed to write {}: {}", readme_path.display(), err))
+}
+
+/// Return true if the `readme` content has the markers of the performance table of the `year`
+pub fn has_section (readme: &str, year: u32) -> bool {
+    section_bounds(readme, year).is_ok()
+}
+
+/// Rewrite the performance table of the `year` in the `readme` content, with the results of
+/// `benchmark_result` followed by a link to the histogram `svg_link`.
+///
+/// The table is expected between the marker comments `<!-- BEGIN perfo-<yyyy> -->` and
+/// `<!-- END perfo-<yyyy> -->`, each one on its own line.
+pub fn update_section<Y> (readme: &str, year: &Y, benchmark_result: &BenchmarkResult, svg_link: &str) -> Result<String>
+where Y : Year + ?Sized {
+
+    let mut section = performance_table(year, benchmark_result, false);
+    section.push(String::new());
+    section.push(format!("![]({})", svg_link));
+
+    replace_section(readme, year.get_year(), &section)
+}
+
+/// Link to the file at `target_path`, relative to the directory of the README file at `readme_path`.
+/// Both files must exist.
+pub fn relative_link (readme_path: &Path, target_path: &Path) -> Result<String> {
+
+    let canonicalize = |path: &Path| path.canonicalize()
+        .map_err(|err| anyhow!("Failed to locate {}: {}", path.display(), err));
+
+    let readme_path = canonicalize(readme_path)?;
+    let readme_dir = readme_path.parent().ok_or(anyhow!("No directory for {}", readme_path.display()))?;
+    Ok(relative_path(readme_dir, &canonicalize(target_path)?))
+}
+
+/// Path going from the absolute directory `from_dir` to the absolute path `to`, with `/` separators
+fn relative_path (from_dir: &Path, to: &Path) -> String {
+
+    let from = from_dir.components().collect_vec();
+    let to = to.components().collect_vec();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let ups = match common == from.len() {
+        true => vec![".".to_string()],
+        false => vec!["..".to_string(); from.len() - common],
+    };
+    let downs = to [common..].iter().filter_map(|component| match component {
+        Component::Normal(name) => Some (name.to_string_lossy().to_string()),
+        _ => None,
+    });
+
+    ups.into_iter().chain(downs).join("/")
+}
+
+/// Line indexes of the begin and end markers of the `year` in the `readme` content
+fn section_bounds (readme: &str, year: u32) -> Result<(usize, usize)> {
+
+    let begin = format!("<!-- BEGIN perfo-{} -->", year);
+    let end = format!("<!-- END perfo-{} -->", year);
+
+    let find = |marker: &str| readme.lines().position(|line| line.trim() == marker)
+        .ok_or(anyhow!("Missing marker '{}' in the README", marker));
+    let (begin_idx, end_idx) = (find(&begin)?, find(&end)?);
+    if end_idx < begin_idx { bail!("The marker '{}' must come after '{}' in the README", end, begin) }
+
+    Ok((begin_idx, end_idx))
+}
+
+/// Replace the lines between the markers of the `year` in the `readme` content by the `section` lines
+fn replace_section (readme: &str, year: u32, section: &[String]) -> Result<String> {
+
+    let (begin_idx, end_idx) = section_bounds(readme, year)?;
+    let lines = readme.lines().collect_vec();
+
+    let new_lines = lines [..=begin_idx].iter().copied()
+        .chain(section.iter().map(|line| line.as_str()))
+        .chain(lines [end_idx..].iter().copied());
+
+    Ok(new_lines.map(|line| format!("{}\n", line)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_section () {
+        let readme = "# Title\n<!-- BEGIN perfo-2024 -->\nold table\n<!-- END perfo-2024 -->\nfooter\n";
+        let section = ["new".to_string(), "table".to_string()];
+
+        let updated = replace_section(readme, 2024, &section).unwrap();
+        assert_eq!(updated, "# Title\n<!-- BEGIN perfo-2024 -->\nnew\ntable\n<!-- END perfo-2024 -->\nfooter\n");
+        assert!(replace_section(readme, 2022, &section).is_err());
+        assert!(has_section(readme, 2024));
+        assert!(!has_section(readme, 2023));
+    }
+
+    #[test]
+    fn test_relative_path () {
+        assert_eq!(relative_path(Path::new("/repo"), Path::new("/repo/out/perfo-2024.svg")), "./out/perfo-2024.svg");
+        assert_eq!(relative_path(Path::new("/repo/docs"), Path::new("/repo/out/perfo-2024.svg")), "../out/perfo-2024.svg");
+        assert_eq!(relative_path(Path::new("/a/b"), Path::new("/c/d.svg")), "../../c/d.svg");
+    }
+
+    #[test]
+    fn test_format_ms () {
+        assert_eq!(format_ms(Duration::from_micros(144)), "0.144");
+        assert_eq!(format_ms(Duration::from_micros(82_530)), "82.53");
+        assert_eq!(format_ms(Duration::from_micros(276_700)), "276.7");
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::Duration;
+use itertools::Itertools;
+use crate::{SolveResult, Year};
+use crate::benchmark::{BenchmarkResult, DurationStats};
+use crate::timing::{Phase, PhaseDurations};
+
+/// Output format of the solve and benchmark results
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Outcome of a single puzzle, as reported in the machine-readable formats
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub name: String,
+    pub part_a: Option<String>,
+    pub part_b: Option<String>,
+    pub error: Option<String>,
+    pub duration: Option<Duration>,
+    pub phases: Option<PhaseDurations>,
+    pub stats: Option<DurationStats>,
+}
+
+/// A single field value of a [Record]
+enum Value {
+    Null,
+    Number (u128),
+    Text (String),
+}
+
+impl Format {
+
+    /// Get the format from its name `name`
+    pub fn from_name (name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv"  => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl Record {
+
+    /// New record for the given `day` of the `year`, without any result yet
+    fn new<Y> (year: &Y, day: u32) -> Record
+    where Y : Year + ?Sized {
+        Record {
+            year: year.get_year(),
+            day,
+            name: year.get_day_name(day).unwrap_or_default().to_string(),
+            part_a: None,
+            part_b: None,
+            error: None,
+            duration: None,
+            phases: None,
+            stats: None,
+        }
+    }
+
+    /// Return the named fields of this record, in order
+    fn fields (&self) -> Vec<(&'static str, Value)> {
+
+        let text = |s: &Option<String>| s.clone().map_or(Value::Null, Value::Text);
+        let nanos = |d: Option<Duration>| d.map_or(Value::Null, |d| Value::Number(d.as_nanos()));
+        let mut fields = vec![
+            ("year", Value::Number(self.year as u128)),
+            ("day", Value::Number(self.day as u128)),
+            ("name", Value::Text(self.name.clone())),
+            ("part_a", text(&self.part_a)),
+            ("part_b", text(&self.part_b)),
+            ("error", text(&self.error)),
+            ("duration_ns", nanos(self.duration)),
+        ];
+
+        // Then the duration of each phase, null for the phases the day does not time separately
+        let phase_fields = ["parse_ns", "part_a_ns", "part_b_ns", "both_parts_ns"];
+        for (name, phase) in phase_fields.into_iter().zip(Phase::iter()) {
+            fields.push((name, nanos(self.phases.and_then(|phases| phases.get_timed(phase)))));
+        }
+
+        // Then the statistics of the benchmark measurements
+        let stats = self.stats.as_ref();
+        fields.push(("samples", stats.map_or(Value::Null, |s| Value::Number(s.samples as u128))));
+        fields.push(("min_ns", nanos(stats.map(|s| s.min))));
+        fields.push(("median_ns", nanos(stats.map(|s| s.median))));
+        fields.push(("p90_ns", nanos(stats.map(|s| s.p90))));
+        fields.push(("p99_ns", nanos(stats.map(|s| s.p99))));
+        fields.push(("std_dev_ns", nanos(stats.map(|s| s.std_dev))));
+        fields
+    }
+}
+
+/// Make the records of the solutions `solve_result` found for the `year`, ordered by day
+pub fn solve_records<Y> (year: &Y, solve_result: &SolveResult) -> Vec<Record>
+where Y : Year + ?Sized {
+
+    solve_result.keys().sorted().map(|&day| {
+        let mut record = Record::new(year, day);
+        match &solve_result[&day] {
+            Ok((a, b, durations)) => {
+                record.part_a = Some(a.to_string());
+                record.part_b = Some(b.to_string());
+                record.duration = Some(durations.total());
+                record.phases = Some(*durations);
+            },
+            Err(err) => record.error = Some(err.to_string()),
+        }
+        record
+    }).collect()
+}
+
+/// Make the records of the benchmark `benchmark_result` of the `year`, ordered by day
+pub fn benchmark_records<Y> (year: &Y, benchmark_result: &BenchmarkResult) -> Vec<Record>
+where Y : Year + ?Sized {
+
+    benchmark_result.keys().sorted().map(|&day| {
+        let mut record = Record::new(year, day);
+        match &benchmark_result[&day] {
+            Ok(day_benchmark) => {
+                record.part_a = Some(day_benchmark.answers.0.clone());
+                record.part_b = Some(day_benchmark.answers.1.clone());
+                record.duration = Some(day_benchmark.stats.mean);
+                record.stats = Some(day_benchmark.stats);
+                record.phases = Some(day_benchmark.phases);
+            },
+            Err(err) => record.error = Some(err.to_string()),
+        }
+        record
+    }).collect()
+}
+
+/// Format the `records` as a JSON array of objects
+pub fn to_json (records: &[Record]) -> String {
+
+    if records.is_empty() { return "[]\n".to_string() }
+
+    let objects = records.iter().map(|record| {
+        let fields = record.fields().into_iter().map(|(name, value)| {
+            let value = match value {
+                Value::Null => "null".to_string(),
+                Value::Number(n) => n.to_string(),
+                Value::Text(s) => json_string(&s),
+            };
+            format!("\"{}\": {}", name, value)
+        }).join(", ");
+        format!("  {{ {} }}", fields)
+    }).join(",\n");
+
+    format!("[\n{}\n]\n", objects)
+}
+
+/// Format the `records` as CSV rows, with a header row
+pub fn to_csv (records: &[Record]) -> String {
+
+    let Some(first) = records.first() else { return String::new() };
+    let header = first.fields().into_iter().map(|(name, _)| name).join(",");
+
+    let rows = records.iter().map(|record| {
+        record.fields().into_iter().map(|(_, value)| match value {
+            Value::Null => String::new(),
+            Value::Number(n) => n.to_string(),
+            Value::Text(s) => csv_string(&s),
+        }).join(",")
+    });
+
+    std::iter::once(header).chain(rows).map(|row| row + "\n").collect()
+}
+
+/// Quote and escape the string `s` to make it a JSON string
+fn json_string (s: &str) -> String {
+
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"'  => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Quote the string `s` if required to make it a CSV field
+fn csv_string (s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string () {
+        assert_eq!(json_string("co,de"), "\"co,de\"");
+        assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+    }
+
+    #[test]
+    fn test_csv_string () {
+        assert_eq!(csv_string("262"), "262");
+        assert_eq!(csv_string("22,20"), "\"22,20\"");
+        assert_eq!(csv_string("a \"b\""), "\"a \"\"b\"\"\"");
+    }
+}
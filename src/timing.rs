@@ -0,0 +1,143 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use itertools::Itertools;
+
+/// The successive phases of solving a daily puzzle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Phase {
+
+    /// Reading the puzzle input into some data structure
+    Parse,
+
+    /// Solving the first part of the puzzle
+    PartA,
+
+    /// Solving the second part of the puzzle
+    PartB,
+
+    /// Solving both parts of the puzzle at once, when they cannot be told apart
+    BothParts,
+}
+
+/// The time spent in each [Phase] of solving a daily puzzle
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PhaseDurations {
+    pub parse: Duration,
+    pub part_a: Duration,
+    pub part_b: Duration,
+    pub both_parts: Duration,
+}
+
+/// Measure the time spent in the successive [phases](Phase) of solving a daily puzzle.
+///
+/// Each call to [lap](PhaseTimer::lap) attributes the time elapsed since the previous call to
+/// some phase. The same phase can be reported several times, in which case the durations add up.
+pub struct PhaseTimer {
+    last_lap: Instant,
+    durations: PhaseDurations,
+}
+
+impl Phase {
+
+    /// Return an iterator on the phases, in the order they usually occur
+    pub fn iter () -> impl Iterator<Item = Phase> {
+        [Phase::Parse, Phase::PartA, Phase::PartB, Phase::BothParts].into_iter()
+    }
+
+    /// Short name of the phase
+    pub fn name (&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::PartA => "part A",
+            Phase::PartB => "part B",
+            Phase::BothParts => "parts A+B",
+        }
+    }
+}
+
+impl PhaseDurations {
+
+    /// Get the time spent in the given `phase`
+    pub fn get (&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::PartA => self.part_a,
+            Phase::PartB => self.part_b,
+            Phase::BothParts => self.both_parts,
+        }
+    }
+
+    /// Get the time spent in the given `phase`, or `None` if the day does not time it separately,
+    /// e.g. the parsing done within the parts
+    pub fn get_timed (&self, phase: Phase) -> Option<Duration> {
+        Some (self.get(phase)).filter(|duration| !duration.is_zero())
+    }
+
+    /// Get a mutable reference on the time spent in the given `phase`
+    pub fn get_mut (&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::PartA => &mut self.part_a,
+            Phase::PartB => &mut self.part_b,
+            Phase::BothParts => &mut self.both_parts,
+        }
+    }
+
+    /// Return the total time spent in all the phases
+    pub fn total (&self) -> Duration {
+        Phase::iter().map(|phase| self.get(phase)).sum()
+    }
+}
+
+impl Display for PhaseDurations {
+
+    /// List the phases that took some time, e.g. `parse: 12µs, part A: 1.2ms, part B: 3ms`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let phases = Phase::iter()
+            .filter_map(|phase| Some (format!("{}: {:?}", phase.name(), self.get_timed(phase)?)))
+            .join(", ");
+        f.write_str(&phases)
+    }
+}
+
+impl PhaseTimer {
+
+    /// New timer, starting to measure the first phase right now
+    pub fn start () -> PhaseTimer {
+        PhaseTimer {
+            last_lap: Instant::now(),
+            durations: PhaseDurations::default(),
+        }
+    }
+
+    /// Attribute the time elapsed since the previous lap (or the start) to the given `phase`
+    pub fn lap (&mut self, phase: Phase) {
+        let now = Instant::now();
+        *self.durations.get_mut(phase) += now - self.last_lap;
+        self.last_lap = now;
+    }
+
+    /// Return the time spent in each phase so far
+    pub fn durations (&self) -> PhaseDurations {
+        self.durations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_durations () {
+        let mut durations = PhaseDurations::default();
+        *durations.get_mut(Phase::Parse) += Duration::from_micros(12);
+        *durations.get_mut(Phase::PartB) += Duration::from_millis(3);
+        *durations.get_mut(Phase::PartB) += Duration::from_millis(1);
+
+        assert_eq!(durations.part_b, Duration::from_millis(4));
+        assert_eq!(durations.total(), Duration::from_micros(4012));
+        assert_eq!(durations.get_timed(Phase::PartA), None);
+        assert_eq!(durations.get_timed(Phase::Parse), Some (Duration::from_micros(12)));
+        assert_eq!(durations.to_string(), "parse: 12µs, part B: 4ms");
+    }
+}
@@ -1,4 +1,7 @@
 
+use std::borrow::Borrow;
+
+/// A set of N-D items, e.g. [CooN](crate::tools::CooN), in a box going from `min` to `max` (both included)
 pub struct ArraySet<const N: usize> {
 
     min: [isize; N],
@@ -9,7 +12,9 @@ pub struct ArraySet<const N: usize> {
 
 impl<const N: usize> ArraySet<N> {
 
-    pub fn new(min: [isize; N], max: [isize; N]) -> ArraySet<N> {
+    pub fn new(min: impl Into<[isize; N]>, max: impl Into<[isize; N]>) -> ArraySet<N> {
+
+        let (min, max) = (min.into(), max.into());
 
         let mut offsets = [0; N];
         let mut total = 1;
@@ -21,16 +26,16 @@ impl<const N: usize> ArraySet<N> {
         ArraySet { min, max, offsets, content: vec![false; total], }
     }
 
-    pub fn test (&self, item: &[isize; N]) -> bool {
+    pub fn test (&self, item: &impl Borrow<[isize; N]>) -> bool {
         self.content [self.index(item)]
     }
 
-    pub fn set (&mut self, item: &[isize; N]) {
+    pub fn set (&mut self, item: &impl Borrow<[isize; N]>) {
         let index = self.index(item);
         self.content [index] = true;
     }
 
-    pub fn toggle (&mut self, item: &[isize; N]) {
+    pub fn toggle (&mut self, item: &impl Borrow<[isize; N]>) {
         let index = self.index(item);
         self.content [index] ^= true;
     }
@@ -39,7 +44,8 @@ impl<const N: usize> ArraySet<N> {
         self.content.iter().filter(|&x| *x).count()
     }
 
-    fn index (&self, item: &[isize; N]) -> usize {
+    fn index (&self, item: &impl Borrow<[isize; N]>) -> usize {
+        let item = item.borrow();
         (0..N).map(|i| {
             assert!(item [i] >= self.min[i]);
             assert!(item [i] <= self.max[i]);
@@ -1,3 +1,5 @@
+use std::borrow::Borrow;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
 use itertools::Itertools;
 
 /// The four possible displacements (up, down, left and right)
@@ -173,12 +175,11 @@ impl Direction {
     }
 }
 
-pub fn find_coo_extents (coos: impl Iterator<Item = Coo>) -> (Coo, Coo) {
+/// Return the lowest and the highest `x` and `y` among the `coos`, or `None` if there is no coordinate
+pub fn find_coo_extents (mut coos: impl Iterator<Item = Coo>) -> Option<(Coo, Coo)> {
 
-    let min_init = Coo { x: isize::MAX, y: isize::MAX };
-    let max_init = Coo { x: isize::MIN, y: isize::MIN };
-
-    let (min, max) = coos.fold((min_init,max_init), |(mut min, mut max), coo| {
+    let first = coos.next()?;
+    let extents = coos.fold((first, first), |(mut min, mut max), coo| {
         min.x = min.x.min(coo.x);
         min.y = min.y.min(coo.y);
         max.x = max.x.max(coo.x);
@@ -187,5 +188,167 @@ pub fn find_coo_extents (coos: impl Iterator<Item = Coo>) -> (Coo, Coo) {
         (min, max)
     });
 
-    (min, max)
-}
\ No newline at end of file
+    Some (extents)
+}
+
+/// A N-D coordinate, e.g. a [Coo3]. Its components can be used as the items of an [ArraySet](crate::tools::ArraySet).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CooN<const N: usize> (pub [isize; N]);
+
+/// A 3-D coordinate
+pub type Coo3 = CooN<3>;
+
+impl<const N: usize> Default for CooN<N> {
+    fn default() -> Self { CooN([0; N]) }
+}
+
+/// Signed array to [CooN]
+impl<const N: usize> From<[isize; N]> for CooN<N> {
+    fn from(components: [isize; N]) -> Self { CooN(components) }
+}
+
+/// [CooN] to signed array
+impl<const N: usize> From<CooN<N>> for [isize; N] {
+    fn from(coo: CooN<N>) -> Self { coo.0 }
+}
+
+/// To use a [CooN] where a signed array is expected, e.g. by an [ArraySet](crate::tools::ArraySet)
+impl<const N: usize> Borrow<[isize; N]> for CooN<N> {
+    fn borrow(&self) -> &[isize; N] { &self.0 }
+}
+
+/// [Coo] to 2-D [CooN]
+impl From<Coo> for CooN<2> {
+    fn from(coo: Coo) -> Self { CooN([coo.x, coo.y]) }
+}
+
+/// 2-D [CooN] to [Coo]
+impl From<CooN<2>> for Coo {
+    fn from(coo: CooN<2>) -> Self { Coo { x: coo.0 [0], y: coo.0 [1] } }
+}
+
+impl<const N: usize> Index<usize> for CooN<N> {
+    type Output = isize;
+    fn index(&self, axis: usize) -> &isize { &self.0 [axis] }
+}
+
+impl<const N: usize> IndexMut<usize> for CooN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut isize { &mut self.0 [axis] }
+}
+
+impl<const N: usize> Add for CooN<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self { CooN(std::array::from_fn(|i| self.0 [i] + rhs.0 [i])) }
+}
+
+impl<const N: usize> Sub for CooN<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self { CooN(std::array::from_fn(|i| self.0 [i] - rhs.0 [i])) }
+}
+
+impl<const N: usize> Neg for CooN<N> {
+    type Output = Self;
+    fn neg(self) -> Self { CooN(self.0.map(|v| -v)) }
+}
+
+impl<const N: usize> Mul<isize> for CooN<N> {
+    type Output = Self;
+    fn mul(self, rhs: isize) -> Self { CooN(self.0.map(|v| v * rhs)) }
+}
+
+impl<const N: usize> AddAssign for CooN<N> {
+    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+}
+
+impl<const N: usize> SubAssign for CooN<N> {
+    fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+}
+
+impl<const N: usize> CooN<N> {
+
+    /// The unit vector along some `axis`
+    pub fn unit (axis: usize) -> Self {
+        let mut coo = Self::default();
+        coo.0 [axis] = 1;
+        coo
+    }
+
+    /// Return the Manhattan distance with `other` coordinate
+    pub fn manhattan_distance (&self, other: &Self) -> usize {
+        (0..N).map(|i| self.0 [i].abs_diff(other.0 [i])).sum()
+    }
+
+    /// Return the Chebyshev distance with `other` coordinate: the largest difference along an axis
+    #[allow(dead_code)]
+    pub fn chebyshev_distance (&self, other: &Self) -> usize {
+        (0..N).map(|i| self.0 [i].abs_diff(other.0 [i])).max().unwrap_or(0)
+    }
+
+    /// Return true if the coordinate is inside the box going from `min` to `max`, both included
+    pub fn is_inside (&self, min: &Self, max: &Self) -> bool {
+        (0..N).all(|i| self.0 [i] >= min.0 [i] && self.0 [i] <= max.0 [i])
+    }
+
+    /// Iterate on the 2N adjacent coordinates sharing a face with this one (6 in 3-D)
+    pub fn iter_adjacent_orthogonal (&self) -> impl Iterator<Item = Self> {
+        let coo = *self;
+        (0..N).flat_map(move |axis| [coo - Self::unit(axis), coo + Self::unit(axis)])
+    }
+
+    /// Iterate on the 3^N - 1 adjacent coordinates, including the diagonals (26 in 3-D)
+    #[allow(dead_code)]
+    pub fn iter_adjacent_all (&self) -> impl Iterator<Item = Self> {
+        let coo = *self;
+        (0..3usize.pow(N as u32))
+            .map(move |code| CooN(std::array::from_fn(|i| coo.0 [i] + (code / 3usize.pow(i as u32) % 3) as isize - 1)))
+            .filter(move |adjacent| *adjacent != coo)
+    }
+}
+
+/// Return the lowest and the highest values of the `coos` along each axis, or `None` if there is no coordinate
+pub fn find_coo_n_extents<const N: usize> (mut coos: impl Iterator<Item = CooN<N>>) -> Option<(CooN<N>, CooN<N>)> {
+
+    let first = coos.next()?;
+    let extents = coos.fold((first, first), |(min, max), coo| {
+        (CooN(std::array::from_fn(|i| min.0 [i].min(coo.0 [i]))),
+         CooN(std::array::from_fn(|i| max.0 [i].max(coo.0 [i]))))
+    });
+
+    Some (extents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coo_n () {
+        let a = Coo3::from([1, 2, 3]);
+        let b = CooN([4, 0, -1]);
+        assert_eq!(a + b, CooN([5, 2, 2]));
+        assert_eq!(a - b, CooN([-3, 2, 4]));
+        assert_eq!(-a * 2, CooN([-2, -4, -6]));
+        assert_eq!(a [2], 3);
+
+        assert_eq!(a.manhattan_distance(&b), 9);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert!(a.is_inside(&CooN([0, 0, 0]), &CooN([3, 3, 3])));
+        assert!(!b.is_inside(&CooN([0, 0, 0]), &CooN([3, 3, 3])));
+
+        let orthogonal: Vec<_> = a.iter_adjacent_orthogonal().collect();
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.iter().all(|coo| coo.manhattan_distance(&a) == 1));
+
+        let all: Vec<_> = a.iter_adjacent_all().collect();
+        assert_eq!(all.len(), 26);
+        assert!(all.iter().all(|coo| coo.chebyshev_distance(&a) == 1));
+
+        assert_eq!(find_coo_n_extents([a, b].into_iter()), Some ((CooN([1, 0, -1]), CooN([4, 2, 3]))));
+        assert_eq!(find_coo_n_extents(std::iter::empty::<Coo3>()), None);
+
+        let coos = [Coo { x: 3, y: -1 }, Coo { x: 0, y: 2 }];
+        assert_eq!(find_coo_extents(coos.into_iter()), Some ((Coo { x: 0, y: -1 }, Coo { x: 3, y: 2 })));
+        assert_eq!(find_coo_extents(std::iter::empty()), None);
+        assert_eq!(Coo::from(CooN::from(Coo { x: 1, y: -2 })), Coo { x: 1, y: -2 });
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use num::PrimInt;
+
+/// A cycle in a sequence of states x0, x1, x2 ... : from the index `start`, the states repeat
+/// every `length` steps, i.e. x(i + length) = x(i) for any i >= `start`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+
+    /// Get the index of the first occurrence of the state reached at index `n`
+    pub fn first_index (&self, n: usize) -> usize {
+        match n < self.start {
+            true => n,
+            false => self.start + (n - self.start) % self.length,
+        }
+    }
+
+    /// Extrapolate the value at index `n` of a quantity that increases by the same amount at each cycle
+    /// (e.g. a height, a count). The function `value_at(i)` gives the known values, and is only called
+    /// for indices up to `start + length`, included.
+    pub fn extrapolate<T, F> (&self, n: usize, value_at: F) -> T
+    where T: PrimInt, F: Fn(usize) -> T {
+
+        if n <= self.start + self.length { return value_at(n) }
+
+        let num_cycles = T::from((n - self.start) / self.length).expect("Too many cycles");
+        let cycle_increase = value_at(self.start + self.length) - value_at(self.start);
+        value_at(self.first_index(n)) + num_cycles * cycle_increase
+    }
+}
+
+/// Find the cycle of the sequence of states starting with `x0`, each state being computed from the
+/// previous one with `next`, thanks to Floyd's "tortoise and hare" algorithm. No state is stored.
+///
+/// The function does not return if the sequence has no cycle.
+#[allow(dead_code)]
+pub fn floyd<S, F> (x0: &S, next: F) -> Cycle
+where S: PartialEq + Clone, F: Fn(&S) -> S {
+
+    // The hare runs twice as fast as the tortoise, they meet somewhere in the cycle
+    let mut tortoise = next(x0);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&next(&hare));
+    }
+
+    // The distance between them is now a multiple of the cycle length. Moving at the same
+    // speed, with the tortoise starting again from x0, they meet at the start of the cycle
+    let mut start = 0;
+    tortoise = x0.clone();
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    // Go once around the cycle
+    let mut length = 1;
+    hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Find the cycle of the sequence of states starting with `x0`, each state being computed from the
+/// previous one with `next`, thanks to Brent's algorithm. It is usually faster than [floyd], by calling
+/// `next` fewer times. No state is stored.
+///
+/// The function does not return if the sequence has no cycle.
+#[allow(dead_code)]
+pub fn brent<S, F> (x0: &S, next: F) -> Cycle
+where S: PartialEq + Clone, F: Fn(&S) -> S {
+
+    // The tortoise teleports to the hare each time the hare has run a power of 2 steps,
+    // until the hare reaches it. The number of steps since the last teleport is the cycle length.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = next(x0);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, they meet at the start of the cycle
+    tortoise = x0.clone();
+    hare = (0..length).fold(x0.clone(), |state, _| next(&state));
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Find the cycle of the sequence of `states`, by storing them until one of them repeats.
+/// It requires more memory than [floyd] or [brent], but each state is only computed once,
+/// and the sequence can be given by any iterator.
+///
+/// Each state comes with a value, e.g. a height, which is returned for all the states read,
+/// the repeated one included. It gives what [Cycle::extrapolate] needs. The value can be `()`
+/// when not needed. Return `None` if the iterator ends first.
+pub fn find_cycle<S, V, I> (states: I) -> Option<(Cycle, Vec<V>)>
+where S: Hash + Eq, I: IntoIterator<Item = (S, V)> {
+
+    let mut seen = HashMap::<S, usize>::new();
+    let mut values = vec![];
+    for (idx, (state, value)) in states.into_iter().enumerate() {
+        values.push(value);
+        if let Some (&start) = seen.get(&state) {
+            return Some ((Cycle { start, length: idx - start }, values));
+        }
+        seen.insert(state, idx);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_detectors () {
+
+        // 2, 5, 26, 677, 330, 901, 802, 205, 26, 677 ...
+        let next = |x: &u32| (x * x + 1) % 1000;
+        let expected = Cycle { start: 2, length: 6 };
+
+        assert_eq!(floyd(&2, next), expected);
+        assert_eq!(brent(&2, next), expected);
+        let states = std::iter::successors(Some (2), |x| Some (next(x))).map(|x| (x, ()));
+        assert_eq!(find_cycle(states).map(|(cycle, _)| cycle), Some (expected));
+        assert_eq!(find_cycle([(1, ()), (2, ()), (3, ())]), None);
+
+        let states = std::iter::successors(Some (2), |x| Some (next(x))).map(|x| (x, x % 10));
+        assert_eq!(find_cycle(states).map(|(_, values)| values), Some (vec![2, 5, 6, 7, 0, 1, 2, 5, 6]));
+
+        assert_eq!(expected.first_index(5), 5);
+        assert_eq!(expected.first_index(20), 2);
+        assert_eq!(expected.extrapolate(30, |idx| idx as u64 * 3), 90);
+    }
+}
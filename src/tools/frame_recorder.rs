@@ -0,0 +1,253 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use anyhow::*;
+use svg::Document;
+use svg::node::element::{Animate, Group, Rectangle};
+use crate::params::ParamSpec;
+use crate::tools::{Cell, GridCell};
+
+/// Directory where the frames are exported
+const OUT_DIR: &str = "out";
+
+/// Duration of each frame of the animated SVG, in seconds
+const FRAME_DURATION: f64 = 0.1;
+
+/// Maximum number of frames kept, whatever the format. Longer simulations are sampled at regular intervals.
+const MAX_FRAMES: usize = 500;
+
+/// Width and height of a cell in the PPM images, in pixels
+const PPM_CELL_SIZE: usize = 4;
+
+/// Background color, for the empty cells
+const BACKGROUND: (u8, u8, u8) = (0x0f, 0x0f, 0x23);
+
+/// Parameter of the days that can record their simulation, giving the [FrameFormat]
+pub const FRAMES_PARAM: ParamSpec = ParamSpec::new("frames", "none", "Export the frames of the simulation into out/: none, svg or ppm");
+
+/// How the frames of a simulation are exported, if ever
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FrameFormat {
+    #[default]
+    None,
+
+    /// A single animated SVG file
+    Svg,
+
+    /// One PPM image per frame
+    Ppm,
+}
+
+impl FromStr for FrameFormat {
+    type Err = Error;
+
+    fn from_str (s: &str) -> Result<FrameFormat> {
+        match s {
+            "none" => Ok(FrameFormat::None),
+            "svg" => Ok(FrameFormat::Svg),
+            "ppm" => Ok(FrameFormat::Ppm),
+            _ => bail!("Unknown frame format '{}', expecting none, svg or ppm", s),
+        }
+    }
+}
+
+/// A single picture of a simulation, as one character per cell (see [Cell::to_char])
+struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+/// Records the successive states of a grid simulation, and exports them as an animation into `out/`.
+/// Nothing is recorded when the [FrameFormat] is `None`, which is the default.
+///
+/// At most [MAX_FRAMES] frames are kept in memory, plus the last one: when there are too many of them,
+/// every other frame is dropped and the next ones are sampled twice less often.
+#[derive(Default)]
+pub struct FrameRecorder {
+    format: FrameFormat,
+    name: String,
+
+    /// The sampled frames, one every `stride` recorded frames
+    frames: Vec<Frame>,
+    stride: usize,
+
+    /// Number of recorded frames so far, sampled or not
+    num_recorded: usize,
+
+    /// The last recorded frame, when not sampled
+    last: Option<Frame>,
+}
+
+impl FrameRecorder {
+
+    /// New recorder exporting its frames in the `format`, as `out/<name>.svg` or `out/<name>/<nnnnn>.ppm`
+    pub fn new (format: FrameFormat, name: &str) -> FrameRecorder {
+        FrameRecorder { format, name: name.to_string(), frames: vec![], stride: 1, num_recorded: 0, last: None }
+    }
+
+    /// Return true if the frames are recorded. Can be used to skip the preparation of a frame.
+    pub fn is_recording (&self) -> bool {
+        self.format != FrameFormat::None
+    }
+
+    /// Record the content of the `grid` as a new frame
+    pub fn record<T: Cell> (&mut self, grid: &GridCell<T>) {
+        self.record_with(grid.width(), grid.height(), |x, y| grid.sample((x, y)).to_char());
+    }
+
+    /// Record a new frame of `width` x `height` cells, the character of each cell being given by `cell(x, y)`
+    pub fn record_with<F> (&mut self, width: usize, height: usize, cell: F)
+    where F: Fn(usize, usize) -> char {
+
+        if !self.is_recording() { return }
+
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let c = cell(x, y);
+                if c.is_ascii() { c as u8 } else { b'?' }
+            })
+            .collect();
+
+        let frame = Frame { width, height, cells };
+        let sampled = self.num_recorded.is_multiple_of(self.stride);
+        self.num_recorded += 1;
+        if !sampled {
+            self.last = Some (frame);
+            return
+        }
+
+        self.last = None;
+        self.frames.push(frame);
+        if self.frames.len() > MAX_FRAMES {
+            self.frames = self.frames.drain(..).step_by(2).collect();
+            self.stride *= 2;
+        }
+    }
+
+    /// Export the recorded frames, if any, and return where they were saved
+    pub fn save (&self) -> Result<Option<PathBuf>> {
+
+        if self.frames.is_empty() { return Ok(None) }
+        let frames = self.frames.iter().chain(self.last.as_ref()).collect::<Vec<_>>();
+
+        let path = match self.format {
+            FrameFormat::None => return Ok(None),
+            FrameFormat::Svg => Self::save_svg(&frames, &Path::new(OUT_DIR).join(format!("{}.svg", self.name)))?,
+            FrameFormat::Ppm => Self::save_ppm(&frames, &Path::new(OUT_DIR).join(&self.name))?,
+        };
+
+        eprintln!("{} of {} frames saved to {}", frames.len(), self.num_recorded, path.display());
+        Ok(Some(path))
+    }
+
+    /// Save the `frames` as a single animated SVG file, at `path`
+    fn save_svg (frames: &[&Frame], path: &Path) -> Result<PathBuf> {
+
+        let width = frames.iter().map(|frame| frame.width).max().unwrap_or_default();
+        let height = frames.iter().map(|frame| frame.height).max().unwrap_or_default();
+        let num_frames = frames.len();
+
+        let mut document = Document::new()
+            .set("viewBox", (0, 0, width, height))
+            .set("shape-rendering", "crispEdges")
+            .add(Rectangle::new().set("width", width).set("height", height).set("fill", hex_color(BACKGROUND)));
+
+        for (idx, frame) in frames.iter().enumerate() {
+
+            // Show the frame during its time slot only, the animation being played in a loop
+            let key_times = [0, idx, idx+1].map(|t| format!("{:.6}", t as f64 / num_frames as f64));
+            let mut group = Group::new()
+                .set("visibility", "hidden")
+                .add(Animate::new()
+                    .set("attributeName", "visibility")
+                    .set("calcMode", "discrete")
+                    .set("values", "hidden;visible;hidden")
+                    .set("keyTimes", key_times.join(";"))
+                    .set("dur", format!("{}s", num_frames as f64 * FRAME_DURATION))
+                    .set("repeatCount", "indefinite"));
+
+            // Draw the runs of identical cells of each row as a single rectangle, skipping the empty ones
+            for y in 0..frame.height {
+                let row = &frame.cells [y * frame.width..(y+1) * frame.width];
+                let mut x = 0;
+                while x < row.len() {
+                    let run = row [x..].iter().take_while(|&&c| c == row [x]).count();
+                    if let Some (color) = cell_color(row [x]) {
+                        group = group.add(Rectangle::new()
+                            .set("x", x).set("y", y)
+                            .set("width", run).set("height", 1)
+                            .set("fill", hex_color(color)));
+                    }
+                    x += run;
+                }
+            }
+            document = document.add(group);
+        }
+
+        if let Some (dir) = path.parent() { fs::create_dir_all(dir)?; }
+        svg::save(path, &document).map_err(|err| anyhow!("Failed to save {}: {}", path.display(), err))?;
+        Ok(path.to_path_buf())
+    }
+
+    /// Save each of the `frames` as a binary PPM image, in the directory `dir`
+    fn save_ppm (frames: &[&Frame], dir: &Path) -> Result<PathBuf> {
+
+        fs::create_dir_all(dir)?;
+        for (idx, frame) in frames.iter().enumerate() {
+
+            let (width, height) = (frame.width * PPM_CELL_SIZE, frame.height * PPM_CELL_SIZE);
+            let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+            for y in 0..height {
+                for x in 0..width {
+                    let c = frame.cells [(y / PPM_CELL_SIZE) * frame.width + x / PPM_CELL_SIZE];
+                    let (r, g, b) = cell_color(c).unwrap_or(BACKGROUND);
+                    image.extend([r, g, b]);
+                }
+            }
+
+            let path = dir.join(format!("{:05}.ppm", idx));
+            fs::write(&path, image).map_err(|err| anyhow!("Failed to save {}: {}", path.display(), err))?;
+        }
+        Ok(dir.to_path_buf())
+    }
+}
+
+/// Color of a cell drawn with the character `c`, or `None` for the empty cells
+fn cell_color (c: u8) -> Option<(u8, u8, u8)> {
+    match c {
+        b'.' | b' ' => None,
+        b'#' => Some((0xcc, 0xcc, 0xcc)),
+        b'@' | b'^' | b'>' | b'<' | b'v' => Some((0xec, 0x00, 0x8c)),
+        b'O' | b'[' | b']' => Some((0xff, 0xcc, 0x00)),
+        b'|' | b'-' | b'+' => Some((0x00, 0x99, 0x00)),
+
+        // Any other character gets its own arbitrary color
+        _ => Some((c.wrapping_mul(97), c.wrapping_mul(59), c.wrapping_mul(31))),
+    }
+}
+
+/// Format a `color` as `#rrggbb`
+fn hex_color ((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_sampling () {
+        let mut recorder = FrameRecorder::new(FrameFormat::Ppm, "test");
+        for idx in 0..1234 {
+            recorder.record_with(1, 1, |_, _| (b'a' + (idx % 26) as u8) as char);
+        }
+
+        // Sampled every 4 frames, the last one being kept
+        assert_eq!(recorder.num_recorded, 1234);
+        assert_eq!(recorder.stride, 4);
+        assert!(recorder.frames.len() <= MAX_FRAMES);
+        assert_eq!(recorder.frames [1].cells, vec![b'e']);
+        assert_eq!(recorder.last.as_ref().map(|frame| frame.cells.clone()), Some (vec![b'a' + (1233 % 26) as u8]));
+    }
+}
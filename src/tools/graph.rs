@@ -0,0 +1,296 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use crate::tools::{compute_all_pair_distances, GraphWeight, Search};
+
+/// Index of a node in a [Graph], given by order of insertion
+pub type NodeIndex = usize;
+
+/// A directed and weighted graph, whose nodes of type `N` (e.g. names read from the puzzle input)
+/// are interned: each distinct node is stored once and is then designated by its [NodeIndex].
+///
+/// Undirected graphs are made of edges in both directions, see [add_undirected_edge](Self::add_undirected_edge).
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, NodeIndex>,
+    edges: Vec<Vec<(NodeIndex, GraphWeight)>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default () -> Self {
+        Graph { nodes: vec![], indices: HashMap::new(), edges: vec![] }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+
+    /// New graph without any node
+    pub fn new () -> Graph<N> {
+        Self::default()
+    }
+
+    /// Add the `node` if it is not known yet, and return its index
+    pub fn add_node (&mut self, node: N) -> NodeIndex {
+        if let Some (&index) = self.indices.get(&node) { return index }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        index
+    }
+
+    /// Add an edge of some `weight` going from the node `from` to the node `to`, adding the nodes if needed
+    pub fn add_edge (&mut self, from: N, to: N, weight: GraphWeight) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges [from].push((to, weight));
+    }
+
+    /// Add edges of some `weight` from the node `a` to the node `b`, and the other way round
+    pub fn add_undirected_edge (&mut self, a: N, b: N, weight: GraphWeight) {
+        self.add_edge(a.clone(), b.clone(), weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// Index of the `node`, if it is part of the graph
+    #[allow(dead_code)]
+    pub fn index (&self, node: &N) -> Option<NodeIndex> {
+        self.indices.get(node).copied()
+    }
+
+    /// The node at some `index`
+    pub fn node (&self, index: NodeIndex) -> &N {
+        &self.nodes [index]
+    }
+
+    /// Number of nodes
+    pub fn num_nodes (&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Iterate on the nodes reached by the edges starting from the node `index`, along with the edge weights
+    pub fn neighbors (&self, index: NodeIndex) -> impl Iterator<Item = (NodeIndex, GraphWeight)> + Clone + '_ {
+        self.edges [index].iter().copied()
+    }
+
+    /// Return true if there is an edge from the node `from` to the node `to`
+    pub fn has_edge (&self, from: NodeIndex, to: NodeIndex) -> bool {
+        self.edges [from].iter().any(|&(index, _)| index == to)
+    }
+
+    /// Number of edges to cross to go from the node `from` to each node, ignoring their weights,
+    /// or `None` for the nodes that cannot be reached. Done with a BFS.
+    #[allow(dead_code)]
+    pub fn bfs (&self, from: NodeIndex) -> Vec<Option<usize>> {
+
+        let mut hops = vec![None; self.num_nodes()];
+        let mut queue = VecDeque::from([from]);
+        hops [from] = Some (0);
+
+        while let Some (index) = queue.pop_front() {
+            let next_hops = hops [index].map(|n| n + 1);
+            for (next, _) in self.neighbors(index) {
+                if hops [next].is_some() { continue }
+                hops [next] = next_hops;
+                queue.push_back(next);
+            }
+        }
+        hops
+    }
+
+    /// Shortest distance from the node `from` to each node, or `None` for the nodes that cannot be reached.
+    /// Done with a Dijkstra [Search].
+    #[allow(dead_code)]
+    pub fn distances_from (&self, from: NodeIndex) -> Vec<Option<GraphWeight>> {
+
+        let fn_next = |&index: &NodeIndex| self.neighbors(index).map(|(next, weight)| (next, weight as usize));
+
+        let mut distances = vec![None; self.num_nodes()];
+        for (index, distance) in Search::dijkstra([from], fn_next) {
+            distances [index] = Some (distance as GraphWeight);
+        }
+        distances
+    }
+
+    /// Matrix of the shortest distances between all the pairs of nodes, see [compute_all_pair_distances]
+    pub fn all_pair_distances (&self) -> Vec<Vec<GraphWeight>> {
+        compute_all_pair_distances(self.num_nodes(), |index| self.neighbors(index))
+    }
+
+    /// For each node, the set of nodes linked to it by an edge in any direction, except itself
+    fn undirected_neighbors (&self) -> Vec<HashSet<NodeIndex>> {
+
+        let mut neighbors = vec![HashSet::new(); self.num_nodes()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges.iter().filter(|&&(to, _)| to != from) {
+                neighbors [from].insert(to);
+                neighbors [to].insert(from);
+            }
+        }
+        neighbors
+    }
+
+    /// Split the nodes into connected components, the edges being followed in both directions.
+    /// The nodes of each component are sorted by index.
+    #[allow(dead_code)]
+    pub fn connected_components (&self) -> Vec<Vec<NodeIndex>> {
+
+        let neighbors = self.undirected_neighbors();
+        let mut visited = vec![false; self.num_nodes()];
+        let mut components = vec![];
+
+        for start in 0..self.num_nodes() {
+            if visited [start] { continue }
+
+            let mut component = vec![];
+            let mut queue = vec![start];
+            visited [start] = true;
+            while let Some (index) = queue.pop() {
+                component.push(index);
+                for &next in neighbors [index].iter() {
+                    if visited [next] { continue }
+                    visited [next] = true;
+                    queue.push(next);
+                }
+            }
+
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Find all the maximal cliques of the graph, the edges being followed in both directions.
+    /// The nodes of each clique are sorted by index.
+    ///
+    /// This function uses the [Bron–Kerbosch algorithm](https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm)
+    /// with pivoting
+    pub fn maximal_cliques (&self) -> Vec<Vec<NodeIndex>> {
+
+        let neighbors = self.undirected_neighbors();
+        let mut cliques = vec![];
+        Self::bron_kerbosch(&neighbors, &mut vec![], (0..self.num_nodes()).collect(), HashSet::new(), &mut cliques);
+        cliques
+    }
+
+    /// Recursive step of [maximal_cliques](Self::maximal_cliques): report in `cliques` all the maximal cliques
+    /// containing the nodes of `clique`, some of the `candidates` and none of the `excluded` nodes
+    fn bron_kerbosch (
+        neighbors: &[HashSet<NodeIndex>],
+        clique: &mut Vec<NodeIndex>,
+        mut candidates: HashSet<NodeIndex>,
+        mut excluded: HashSet<NodeIndex>,
+        cliques: &mut Vec<Vec<NodeIndex>>) {
+
+        // Nothing can be added: the clique is maximal
+        let Some (&pivot) = candidates.union(&excluded)
+            .max_by_key(|&&index| neighbors [index].intersection(&candidates).count()) else {
+            let mut clique = clique.clone();
+            clique.sort();
+            cliques.push(clique);
+            return
+        };
+
+        // Any maximal clique contains the pivot or one of its non-neighbors
+        let to_try: Vec<NodeIndex> = candidates.difference(&neighbors [pivot]).copied().collect();
+        for index in to_try {
+            clique.push(index);
+            Self::bron_kerbosch(
+                neighbors,
+                clique,
+                candidates.intersection(&neighbors [index]).copied().collect(),
+                excluded.intersection(&neighbors [index]).copied().collect(),
+                cliques
+            );
+            clique.pop();
+
+            candidates.remove(&index);
+            excluded.insert(index);
+        }
+    }
+
+    /// Find a cycle following the directed edges, if any. The cycle is given as the list of its nodes,
+    /// each node having an edge to the next one, and the last one having an edge to the first one.
+    #[allow(dead_code)]
+    pub fn find_cycle (&self) -> Option<Vec<NodeIndex>> {
+
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        enum Visit { New, InProgress, Done }
+
+        let mut visits = vec![Visit::New; self.num_nodes()];
+        for start in 0..self.num_nodes() {
+            if visits [start] != Visit::New { continue }
+
+            // DFS, the stack holding the current path and the index of the next edge to follow from each node
+            let mut stack = vec![(start, 0)];
+            visits [start] = Visit::InProgress;
+            while let Some ((index, edge)) = stack.pop() {
+
+                let Some (&(next, _)) = self.edges [index].get(edge) else {
+                    visits [index] = Visit::Done;
+                    continue
+                };
+                stack.push((index, edge + 1));
+
+                match visits [next] {
+                    Visit::Done => {},
+                    Visit::New => {
+                        visits [next] = Visit::InProgress;
+                        stack.push((next, 0));
+                    },
+
+                    // The edge comes back on the current path
+                    Visit::InProgress => {
+                        let first = stack.iter().position(|&(index, _)| index == next).unwrap();
+                        return Some (stack [first..].iter().map(|&(index, _)| index).collect());
+                    },
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph () {
+
+        // A square a-b-c-d with the diagonal a-c, and a separate pair e-f
+        let mut graph = Graph::new();
+        for (a, b, weight) in [("a", "b", 1), ("b", "c", 1), ("c", "d", 2), ("d", "a", 5), ("a", "c", 4), ("e", "f", 1)] {
+            graph.add_undirected_edge(a, b, weight);
+        }
+        let [a, b, c, d, e, f] = ["a", "b", "c", "d", "e", "f"].map(|name| graph.index(&name).unwrap());
+        assert_eq!(graph.num_nodes(), 6);
+        assert_eq!(*graph.node(c), "c");
+        assert!(graph.has_edge(c, a));
+
+        assert_eq!(graph.bfs(a), vec![Some (0), Some (1), Some (1), Some (1), None, None]);
+        assert_eq!(graph.distances_from(a), vec![Some (0), Some (1), Some (2), Some (4), None, None]);
+        assert_eq!(graph.all_pair_distances() [d][b], 3);
+
+        assert_eq!(graph.connected_components(), vec![vec![a, b, c, d], vec![e, f]]);
+
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![a, b, c], vec![a, c, d], vec![e, f]]);
+
+        // Undirected edges make cycles of 2 nodes
+        assert_eq!(graph.find_cycle().map(|cycle| cycle.len()), Some (2));
+    }
+
+    #[test]
+    fn test_find_cycle () {
+        let mut graph = Graph::new();
+        for (from, to) in [(0, 1), (1, 2), (0, 2), (2, 3)] {
+            graph.add_edge(from, to, 1);
+        }
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.add_edge(3, 1, 1);
+        assert_eq!(graph.find_cycle(), Some (vec![1, 2, 3]));
+    }
+}
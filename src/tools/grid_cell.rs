@@ -1,9 +1,7 @@
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 use std::fmt::Display;
 use anyhow::{anyhow, bail};
 use itertools::Itertools;
-use crate::tools::Coo;
+use crate::tools::{Coo, Search};
 
 /// Models a rectangular area made of generic [Cell]
 #[derive(Clone)]
@@ -24,30 +22,6 @@ pub trait Cell: Sized + Default + Clone {
 }
 
 
-/// Next element to explore with Dijkstra
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-struct DijkstraItem {
-    coo: Coo,
-    score: usize,
-}
-
-/// Dijkstra priority queue
-type PriorityQueue = BinaryHeap<DijkstraItem>;
-
-/// Ordering for [DijkstraItem] elements in the [PriorityQueue]
-impl Ord for DijkstraItem {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
-    }
-}
-
-/// Ordering for [DijkstraItem] elements in the [PriorityQueue]
-impl PartialOrd for DijkstraItem {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 /// To help debugging
 impl<T: Cell> Display for GridCell<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -224,40 +198,17 @@ impl<T: Cell> GridCell<T> {
     ///
     /// This function implements a Dijkstra algorithm that begins its exploration at coordinate `from`.
     /// Then, it uses the provided `fn_adjacency` function to progress across its unexplored neighborhood.
-    /// Each discovered cell get a score resulting from the parent cell + the adjacency weight.
-    /// The iteration stops when there is no cell left to explore.
+    /// Each discovered cell get a score resulting from the parent cell + 1. The iteration stops when there
+    /// is no cell left to explore. See [Search] for weighted moves, several starts or custom states.
     pub fn iter_dijkstra<F, I> (&self, from: Coo, fn_adjacency: F) -> impl Iterator<Item = (Coo, &T, usize)>
     where
         F: Fn(Coo) -> I,
         I: Iterator<Item = Coo> {
 
-        let mut visited = vec! [false; self.cells.len()];
-        let mut pq = PriorityQueue::new ();
-
-        let start = DijkstraItem { coo: from, score: 0 };
-        pq.push (start);
-
-        std::iter::from_fn(move || {
-
-            if let Some (item) = pq.pop() {
-
-                let notify = (item.coo, self.sample(item.coo), item.score);
-                let adjacency = fn_adjacency (item.coo);
-
-                for next_coo in adjacency {
-                    let index = self.index(&next_coo);
-                    if visited[index] { continue }
-                    visited[index] = true;
-                    pq.push(DijkstraItem { coo: next_coo, score: item.score+1 })
-                }
-
-                Some (notify)
-            }
-
-            else {
-                None
-            }
-        })
+        let fn_next = move |&coo: &Coo| fn_adjacency(coo).map(|next_coo| (next_coo, 1));
+        let fn_index = |coo: &Coo| self.index(coo);
+        Search::dijkstra_indexed([from], fn_next, self.area(), fn_index)
+            .map(|(coo, score)| (coo, self.sample(coo), score))
     }
 
     /// Return a flat 1D coordinate
@@ -7,16 +7,26 @@ mod int_intervals;
 mod grid_cell;
 mod bit_set;
 mod array_set;
+mod ocr;
+mod frame_recorder;
+mod search;
+mod graph;
 
 use num::Integer;
 
-pub use coordinates::{Direction, Coo, find_coo_extents};
-pub use topo_sort::{TopoSortElement, topo_sort};
+pub use coordinates::{Direction, Coo, CooN, Coo3, find_coo_extents, find_coo_n_extents};
+pub use topo_sort::{TopoSortElement, topo_sort_kahn};
 pub use all_pair_distances::*;
 pub use int_intervals::{IntInterval, IntIntervals};
 pub use grid_cell::{Cell, GridCell};
 pub use array_set::ArraySet;
 pub use bit_set::BitSet;
+#[allow(unused_imports)]
+pub use cycle_detector::{Cycle, floyd, brent, find_cycle};
+pub use ocr::read_letters;
+pub use frame_recorder::{FrameRecorder, FRAMES_PARAM};
+pub use search::Search;
+pub use graph::{Graph, NodeIndex};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Sign { Positive, Negative }
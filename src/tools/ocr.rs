@@ -0,0 +1,93 @@
+use anyhow::*;
+
+/// Width of a letter of the font, in pixels
+const GLYPH_WIDTH: usize = 4;
+
+/// Height of a letter of the font, in pixels
+const GLYPH_HEIGHT: usize = 6;
+
+/// Horizontal distance between two consecutive letters: a blank column separates them
+const GLYPH_PITCH: usize = GLYPH_WIDTH + 1;
+
+/// The letters of the 4x6 font drawn by the puzzles, row by row. A `#` is a lit pixel.
+const FONT: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Encode the 24 pixels of a letter as a bit mask, the first pixel being the most significant bit
+fn glyph_mask (pixels: impl Iterator<Item = bool>) -> u32 {
+    pixels.fold(0, |mask, lit| (mask << 1) | lit as u32)
+}
+
+/// Read the letters drawn on a screen of `width` x 6 pixels, with the 4x6 font of the puzzles.
+/// The pixel at column `x` and row `y` is lit when `is_lit(x, y)` returns true.
+///
+/// The letters are expected every 5 columns, starting from the first one.
+pub fn read_letters<F> (width: usize, is_lit: F) -> Result<String>
+where F: Fn(usize, usize) -> bool {
+
+    (0..width.div_ceil(GLYPH_PITCH)).map(|idx| {
+        let left = idx * GLYPH_PITCH;
+        let pixels = (0..GLYPH_HEIGHT).flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)));
+        let mask = glyph_mask(pixels.map(|(x, y)| x < width && is_lit(x, y)));
+
+        FONT.iter()
+            .find(|(_, glyph)| glyph_mask(glyph.bytes().map(|b| b == b'#')) == mask)
+            .map(|&(letter, _)| letter)
+            .ok_or(anyhow!("Unknown letter at column {}", left))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read the letters drawn by the `rows` of a picture, where the lit pixels are `#` characters
+    fn read_picture (rows: &[&str]) -> Result<String> {
+        let width = rows [0].len();
+        read_letters(width, |x, y| rows [y].as_bytes() [x] == b'#')
+    }
+
+    #[test]
+    fn test_read_picture () {
+        let picture = [
+            "###   ##   ##  #### #  # ",
+            "#  # #  # #  # #    # #  ",
+            "###  #  # #    ###  ##   ",
+            "#  # #### #    #    # #  ",
+            "#  # #  # #  # #    # #  ",
+            "###  #  #  ##  #### #  # ",
+        ];
+        assert_eq!(read_picture(&picture).unwrap(), "BACEK");
+        assert!(read_picture(&["#", "#", "#", "#", "#", "#"]).is_err());
+    }
+
+    #[test]
+    fn test_read_font () {
+        let picture = [
+            " ##  ###   ##  #### ####  ##  #  #  ###   ## #  # #     ##  ###  ###   ### #  # #### ",
+            "#  # #  # #  # #    #    #  # #  #   #     # # #  #    #  # #  # #  # #    #  #    # ",
+            "#  # ###  #    ###  ###  #    ####   #     # ##   #    #  # #  # #  # #    #  #   #  ",
+            "#### #  # #    #    #    # ## #  #   #     # # #  #    #  # ###  ###   ##  #  #  #   ",
+            "#  # #  # #  # #    #    #  # #  #   #  #  # # #  #    #  # #    # #     # #  # #    ",
+            "#  # ###   ##  #### #     ### #  #  ###  ##  #  # ####  ##  #    #  # ###   ##  #### ",
+        ];
+        assert_eq!(read_picture(&picture).unwrap(), "ABCEFGHIJKLOPRSUZ");
+    }
+}
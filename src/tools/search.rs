@@ -0,0 +1,340 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// Next state to explore, with its cost from the closest start and its priority (cost + heuristic)
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct SearchItem<S> {
+    state: S,
+    cost: usize,
+    priority: usize,
+}
+
+/// Ordering for [SearchItem] elements in the priority queue: lowest priority first
+impl<S: Eq> Ord for SearchItem<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Ordering for [SearchItem] elements in the priority queue
+impl<S: Eq> PartialOrd for SearchItem<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// What is known about a state discovered by the search
+#[derive(Debug, Copy, Clone)]
+pub struct SearchNode {
+
+    /// Lowest cost found so far to reach this state
+    cost: usize,
+
+    /// True once the state has been yielded: its cost is final
+    settled: bool,
+}
+
+/// Storage of the [SearchNode] of each state discovered by a [Search]
+pub trait SearchNodes<S> {
+
+    /// The node of the `state`, if it has been discovered
+    fn get (&self, state: &S) -> Option<SearchNode>;
+
+    /// Mutable reference on the node of the `state`, if it has been discovered
+    fn get_mut (&mut self, state: &S) -> Option<&mut SearchNode>;
+
+    /// Record the `node` of a newly discovered `state`
+    fn insert (&mut self, state: S, node: SearchNode);
+}
+
+/// Default storage, for any hashable state
+impl<S: Eq + Hash> SearchNodes<S> for HashMap<S, SearchNode> {
+
+    fn get (&self, state: &S) -> Option<SearchNode> { HashMap::get(self, state).copied() }
+
+    fn get_mut (&mut self, state: &S) -> Option<&mut SearchNode> { HashMap::get_mut(self, state) }
+
+    fn insert (&mut self, state: S, node: SearchNode) { HashMap::insert(self, state, node); }
+}
+
+/// Dense storage, for states that `fn_index` maps to indices below a known bound, e.g. the cells of a grid.
+/// It avoids hashing the states, see [Search::dijkstra_indexed].
+pub struct IndexedNodes<X> {
+    nodes: Vec<Option<SearchNode>>,
+    fn_index: X,
+}
+
+impl<S, X: Fn(&S) -> usize> SearchNodes<S> for IndexedNodes<X> {
+
+    fn get (&self, state: &S) -> Option<SearchNode> { self.nodes [(self.fn_index)(state)] }
+
+    fn get_mut (&mut self, state: &S) -> Option<&mut SearchNode> { self.nodes [(self.fn_index)(state)].as_mut() }
+
+    fn insert (&mut self, state: S, node: SearchNode) { self.nodes [(self.fn_index)(&state)] = Some (node); }
+}
+
+/// Heuristic of a plain Dijkstra search
+type NoHeuristic<S> = fn(&S) -> usize;
+
+/// A Dijkstra or A* search over generic states `S`, e.g. a [Coo](crate::tools::Coo) or a `(Coo, Direction)`.
+///
+/// The search is an iterator yielding the pairs `(state, cost)` by increasing cost (or increasing cost + heuristic
+/// with A*), each state being yielded once with its lowest cost. The iteration can be stopped at any time, e.g. when
+/// the goal is reached, and the search then tells the [cost](Self::cost) of the states discovered so far, and their
+/// [predecessors](Self::predecessors) if the search records them, see [with_predecessors](Self::with_predecessors).
+///
+/// What is known about the states is stored in a hash map, unless they are given indices, see [SearchNodes].
+pub struct Search<S, F, H = NoHeuristic<S>, N = HashMap<S, SearchNode>> {
+    queue: BinaryHeap<SearchItem<S>>,
+    nodes: N,
+    predecessors: Option<HashMap<S, Vec<S>>>,
+    fn_next: F,
+    fn_heuristic: H,
+}
+
+impl<S, F, I> Search<S, F, NoHeuristic<S>>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)> {
+
+    /// Start a Dijkstra search from all the `starts` states at once. The function `fn_next(state)` gives
+    /// the states that can be reached from `state`, along with the weight of each move.
+    pub fn dijkstra<T> (starts: T, fn_next: F) -> Self
+    where T: IntoIterator<Item = S> {
+        Search::a_star(starts, fn_next, |_| 0)
+    }
+}
+
+impl<S, F, I, X> Search<S, F, NoHeuristic<S>, IndexedNodes<X>>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    X: Fn(&S) -> usize {
+
+    /// Same as [dijkstra](Search::dijkstra), for states that `fn_index(state)` maps to indices below `num_states`,
+    /// e.g. the cells of a grid. What is known about each state is then stored in a vector, which is faster than hashing.
+    pub fn dijkstra_indexed<T> (starts: T, fn_next: F, num_states: usize, fn_index: X) -> Self
+    where T: IntoIterator<Item = S> {
+        let nodes = IndexedNodes { nodes: vec![None; num_states], fn_index };
+        Search::with_nodes(nodes, starts, fn_next, |_| 0)
+    }
+}
+
+impl<S, F, I, H> Search<S, F, H>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: Fn(&S) -> usize {
+
+    /// Start a A* search from all the `starts` states at once. The function `fn_next(state)` gives the
+    /// states that can be reached from `state`, along with the weight of each move. The function
+    /// `fn_heuristic(state)` estimates the remaining cost to the goal: it must never overestimate it,
+    /// and must not decrease by more than the weight of a move.
+    pub fn a_star<T> (starts: T, fn_next: F, fn_heuristic: H) -> Self
+    where T: IntoIterator<Item = S> {
+        Search::with_nodes(HashMap::new(), starts, fn_next, fn_heuristic)
+    }
+}
+
+impl<S, F, I, H, N> Search<S, F, H, N>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: Fn(&S) -> usize,
+    N: SearchNodes<S> {
+
+    /// Start a search storing what is known about the states in `nodes`, see [a_star](Search::a_star)
+    fn with_nodes<T> (nodes: N, starts: T, fn_next: F, fn_heuristic: H) -> Self
+    where T: IntoIterator<Item = S> {
+
+        let mut search = Search { queue: BinaryHeap::new(), nodes, predecessors: None, fn_next, fn_heuristic };
+        for state in starts {
+            if search.nodes.get(&state).is_some() { continue }
+            search.nodes.insert(state.clone(), SearchNode { cost: 0, settled: false });
+            let priority = (search.fn_heuristic)(&state);
+            search.queue.push(SearchItem { state, cost: 0, priority });
+        }
+        search
+    }
+
+    /// Record the predecessors of the states, to tell the lowest cost paths. It must be called before
+    /// the search starts, and is not the default as it costs an allocation for each state discovered.
+    pub fn with_predecessors (mut self) -> Self {
+        self.predecessors = Some (HashMap::new());
+        self
+    }
+
+    /// Lowest cost found so far to reach the `state`. It is final once the state has been yielded.
+    #[allow(dead_code)]
+    pub fn cost (&self, state: &S) -> Option<usize> {
+        self.nodes.get(state).map(|node| node.cost)
+    }
+
+    /// The states from which the `state` is reached with its lowest cost (none for the starts).
+    /// The list is complete once the state has been yielded. Requires [with_predecessors](Self::with_predecessors).
+    pub fn predecessors (&self, state: &S) -> &[S] {
+        self.predecessors.as_ref()
+            .and_then(|predecessors| predecessors.get(state))
+            .map(|predecessors| predecessors.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// One of the lowest cost paths from a start to the `state`, both included.
+    /// Requires [with_predecessors](Self::with_predecessors).
+    #[allow(dead_code)]
+    pub fn path_to (&self, state: &S) -> Option<Vec<S>> {
+
+        self.nodes.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some (previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some (path)
+    }
+
+    /// All the states on any of the lowest cost paths from a start to one of the `targets`, targets included.
+    /// Requires [with_predecessors](Self::with_predecessors).
+    pub fn states_on_paths_to<T> (&self, targets: T) -> HashSet<S>
+    where T: IntoIterator<Item = S> {
+
+        let mut states = HashSet::new();
+        let mut queue: Vec<S> = targets.into_iter().filter(|state| self.nodes.get(state).is_some()).collect();
+        while let Some (state) = queue.pop() {
+            if states.contains(&state) { continue }
+            queue.extend(self.predecessors(&state).iter().cloned());
+            states.insert(state);
+        }
+        states
+    }
+}
+
+impl<S, F, I, H, N> Iterator for Search<S, F, H, N>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: Fn(&S) -> usize,
+    N: SearchNodes<S> {
+
+    type Item = (S, usize);
+
+    fn next (&mut self) -> Option<Self::Item> {
+
+        while let Some (item) = self.queue.pop() {
+
+            // Skip the outdated items: the state has been pushed again since, with a lower cost
+            let node = self.nodes.get_mut(&item.state).expect("Queued states are known");
+            if node.settled || item.cost > node.cost { continue }
+            node.settled = true;
+
+            for (next, weight) in (self.fn_next)(&item.state) {
+                let cost = item.cost + weight;
+                match self.nodes.get_mut(&next) {
+                    Some (node) => {
+
+                        // Another lowest cost path, possibly to a settled state through a move of weight 0.
+                        // The starts keep no predecessors, so that the paths always end on them.
+                        if cost == node.cost {
+                            let predecessors = self.predecessors.as_mut().and_then(|predecessors| predecessors.get_mut(&next));
+                            if let Some (predecessors) = predecessors {
+                                predecessors.push(item.state.clone());
+                            }
+                            continue
+                        }
+                        if node.settled || cost > node.cost { continue }
+                        node.cost = cost;
+                    },
+                    None => self.nodes.insert(next.clone(), SearchNode { cost, settled: false }),
+                }
+
+                if let Some (predecessors) = self.predecessors.as_mut() {
+                    predecessors.insert(next.clone(), vec![item.state.clone()]);
+                }
+                let priority = cost + (self.fn_heuristic)(&next);
+                self.queue.push(SearchItem { state: next, cost, priority });
+            }
+
+            return Some ((item.state, item.cost));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted moves of a small graph: 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 5, 3 -> 4 costs 1
+    fn graph_next (state: &u32) -> Vec<(u32, usize)> {
+        match state {
+            0 => vec![(1, 1), (2, 3)],
+            1 => vec![(3, 4)],
+            2 => vec![(3, 2)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra () {
+        let mut search = Search::dijkstra([0], graph_next).with_predecessors();
+        let visits: Vec<_> = search.by_ref().collect();
+        assert_eq!(visits, vec![(0, 0), (1, 1), (2, 3), (3, 5), (4, 6)]);
+
+        assert_eq!(search.cost(&4), Some (6));
+        assert_eq!(search.predecessors(&3), &[1, 2]);
+        assert_eq!(search.path_to(&4), Some (vec![0, 1, 3, 4]));
+        assert_eq!(search.states_on_paths_to([3]), HashSet::from([0, 1, 2, 3]));
+
+        // Same order with the states stored by index
+        let search = Search::dijkstra_indexed([0], graph_next, 5, |&state: &u32| state as usize);
+        assert_eq!(search.collect::<Vec<_>>(), visits);
+
+        // Several starts, stopping early
+        let mut search = Search::dijkstra([1, 2], graph_next).with_predecessors();
+        assert_eq!(search.find(|&(state, _)| state == 3), Some ((3, 2)));
+        assert_eq!(search.path_to(&3), Some (vec![2, 3]));
+    }
+
+    #[test]
+    fn test_zero_weight () {
+
+        // 0 -> 2 costs 1, directly or through 1 with a move of weight 0. 1 and 2 have the same cost:
+        // 2 happens to be settled first, and the move 1 -> 2 is found afterwards.
+        let fn_next = |state: &u32| match state {
+            0 => vec![(2, 1), (1, 1)],
+            1 => vec![(2, 0)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        let mut search = Search::dijkstra([0], fn_next).with_predecessors();
+        let visits: Vec<_> = search.by_ref().collect();
+        assert_eq!(visits, vec![(0, 0), (2, 1), (1, 1), (3, 2)]);
+
+        assert_eq!(search.predecessors(&2), &[0, 1]);
+        assert_eq!(search.states_on_paths_to([3]), HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_a_star () {
+
+        // Walk on an open grid, only the cells along the straight line are explored
+        let target = (5, 0);
+        let fn_next = |&(x, y): &(i32, i32)| [(x+1, y), (x-1, y), (x, y+1), (x, y-1)].map(|coo| (coo, 1));
+        let fn_heuristic = |&(x, y): &(i32, i32)| ((target.0 - x).abs() + (target.1 - y).abs()) as usize;
+
+        let mut search = Search::a_star([(0, 0)], fn_next, fn_heuristic);
+        let explored: Vec<_> = search.by_ref().take_while(|&(coo, _)| coo != target).collect();
+        assert_eq!(explored.len(), 5);
+        assert_eq!(search.cost(&target), Some (5));
+    }
+}
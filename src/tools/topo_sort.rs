@@ -1,6 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt::Debug;
 use std::hash::Hash;
+use anyhow::*;
+use itertools::Itertools;
 
 /// Element that can be topologically sorted with the function [topo_sort]
 pub trait TopoSortElement<I>  {
@@ -10,49 +13,154 @@ pub trait TopoSortElement<I>  {
     fn what_before(&self) -> Self::Iter;
 }
 
+/// State of an element during the DFS of [topo_sort]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mark {
+
+    /// Its predecessors are being visited: meeting it again means there is a cycle
+    InProgress,
+
+    /// It has been added to the sorted vector
+    Done,
+}
+
 /// Given an unsorted map of `items` of type [T], identified by values of type [I],
 /// return a vector of identifiers that are topologically sorted. This means that
 /// the successors of any element in this vector are ensured to appear at a higher
 /// index in this vector.
-pub fn topo_sort<I: Copy + Eq + Hash + Debug, T> (items: &HashMap<I, T>) -> Vec<I>
+///
+/// The function fails if the predecessors form a cycle, or if a predecessor is not in `items`.
+/// The error names the offending identifiers.
+pub fn topo_sort<I: Copy + Eq + Hash + Debug, T> (items: &HashMap<I, T>) -> Result<Vec<I>>
 where T: TopoSortElement<I> {
 
-    let mut visited = HashSet::<I>::new ();
-    let mut dfs_queue = Vec::<(I, &T)>::new();
+    let mut marks = HashMap::<I, Mark>::new ();
     let mut heap = Vec::<I>::new();
 
     // Process all unvisited elements of the hash map
     for (id, item_ref) in items {
-        if visited.contains(id) { continue }
+        if marks.contains_key(id) { continue }
+
+        // The DFS stack holds the current path, along with the predecessors left to visit for each element
+        let mut dfs_stack = vec![(*id, item_ref.what_before())];
+        marks.insert(*id, Mark::InProgress);
 
-        // Push the next unvisited element into the DFS queue, then start processing it
-        dfs_queue.push((*id, item_ref));
-        while let Some((id, item_ref)) = dfs_queue.pop() {
+        while let Some ((id, predecessors)) = dfs_stack.last_mut() {
+            let id = *id;
+            match predecessors.next() {
 
-            // Check if all the successors of the current item are visited
-            let all_next_visited = item_ref.what_before().all (|nid| {
-                visited.contains(&nid)
-            });
+                // All the predecessors are processed, we can add the current item onto the heap
+                None => {
+                    heap.push(id);
+                    marks.insert(id, Mark::Done);
+                    dfs_stack.pop();
+                },
 
-            // If yes, we can add the current item onto the heap and mark it as visited
-            if all_next_visited {
-                heap.push(id);
-                visited.insert(id);
+                // Otherwise, process the next predecessor first
+                Some (next_id) => match marks.get(&next_id) {
+                    Some (Mark::Done) => {},
+                    Some (Mark::InProgress) => {
+                        let start = dfs_stack.iter().position(|(id, _)| *id == next_id).unwrap();
+                        let cycle = dfs_stack [start..].iter().rev().map(|(id, _)| format!("{:?}", id));
+                        bail!("Cycle found: {} -> {:?}", cycle.format(" -> "), dfs_stack.last().unwrap().0);
+                    },
+                    None => {
+                        let next = items.get(&next_id)
+                            .ok_or_else(|| anyhow!("Unknown id {:?}, predecessor of {:?}", next_id, id))?;
+                        marks.insert(next_id, Mark::InProgress);
+                        dfs_stack.push((next_id, next.what_before()));
+                    },
+                },
             }
-            // Otherwise, reschedule a visit of the current item after its
-            // successors have been processed first
-            else {
-                dfs_queue.push((id, item_ref));
-
-                for next_id in item_ref.what_before() {
-                    if !visited.contains(&next_id) {
-                        let next = items.get(&next_id).unwrap();
-                        dfs_queue.push((next_id, next));
-                    }
-                };
+        }
+    }
+
+    Ok(heap)
+}
+
+/// Same as [topo_sort], with the Kahn's algorithm: among the elements whose predecessors are
+/// all sorted, the lowest identifier comes first. The order is thus always the same.
+pub fn topo_sort_kahn<I: Copy + Ord + Hash + Debug, T> (items: &HashMap<I, T>) -> Result<Vec<I>>
+where T: TopoSortElement<I> {
+
+    // Count the predecessors of each element, and list its successors
+    let mut num_predecessors = HashMap::<I, usize>::new();
+    let mut successors = HashMap::<I, Vec<I>>::new();
+    for (id, item) in items {
+        num_predecessors.entry(*id).or_default();
+        for pred_id in item.what_before() {
+            if !items.contains_key(&pred_id) {
+                bail!("Unknown id {:?}, predecessor of {:?}", pred_id, id);
             }
+            *num_predecessors.entry(*id).or_default() += 1;
+            successors.entry(pred_id).or_default().push(*id);
+        }
+    }
+
+    // Process the elements without predecessors left, lowest first
+    let mut ready: BinaryHeap<Reverse<I>> = num_predecessors.iter()
+        .filter(|(_, &count)| count == 0)
+        .map(|(&id, _)| Reverse(id))
+        .collect();
+
+    let mut sorted = Vec::with_capacity(items.len());
+    while let Some (Reverse(id)) = ready.pop() {
+        sorted.push(id);
+        for next_id in successors.get(&id).into_iter().flatten() {
+            let count = num_predecessors.get_mut(next_id).unwrap();
+            *count -= 1;
+            if *count == 0 { ready.push(Reverse(*next_id)); }
         }
     }
 
-    heap
+    // The elements left wait for each other: let the DFS name one of the cycles
+    if sorted.len() < items.len() {
+        return Err(topo_sort(items).err().unwrap_or_else(|| anyhow!("Cycle found")));
+    }
+
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An element listing its predecessors
+    struct Node (Vec<u32>);
+
+    impl TopoSortElement<u32> for Node {
+        type Iter = std::vec::IntoIter<u32>;
+        fn what_before(&self) -> Self::Iter { self.0.clone().into_iter() }
+    }
+
+    fn make_items (predecessors: &[(u32, &[u32])]) -> HashMap<u32, Node> {
+        predecessors.iter().map(|&(id, before)| (id, Node(before.to_vec()))).collect()
+    }
+
+    #[test]
+    fn test_topo_sort () {
+        let items = make_items(&[(1, &[]), (2, &[1]), (3, &[1]), (4, &[3, 2]), (5, &[])]);
+
+        let sorted = topo_sort(&items).unwrap();
+        assert_eq!(sorted.len(), 5);
+        for (id, item) in items.iter() {
+            let pos = sorted.iter().position(|x| x == id).unwrap();
+            assert!(item.0.iter().all(|pred| sorted.iter().position(|x| x == pred).unwrap() < pos));
+        }
+
+        assert_eq!(topo_sort_kahn(&items).unwrap(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_topo_sort_errors () {
+        let items = make_items(&[(1, &[]), (2, &[1, 4]), (3, &[2]), (4, &[3])]);
+        let error = topo_sort(&items).unwrap_err().to_string();
+        assert!(error.starts_with("Cycle found"));
+        assert!(["2", "3", "4"].iter().all(|id| error.contains(id)));
+        assert!(topo_sort_kahn(&items).unwrap_err().to_string().starts_with("Cycle found"));
+
+        let items = make_items(&[(1, &[]), (2, &[1, 7])]);
+        assert_eq!(topo_sort(&items).unwrap_err().to_string(), "Unknown id 7, predecessor of 2");
+        assert_eq!(topo_sort_kahn(&items).unwrap_err().to_string(), "Unknown id 7, predecessor of 2");
+    }
 }
@@ -0,0 +1,147 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
+use anyhow::*;
+use std::result::Result::Ok;
+use crate::{solve_day, Solution, Year};
+use crate::input::InputSource;
+use crate::params::Params;
+
+/// Default directory where the expected answers are stored, as `<dir>/<yyyy>/<dd>.txt`.
+/// Each file contains two lines: the answer to part A, then the answer to part B.
+pub const DEFAULT_ANSWERS_DIR: &str = "answers";
+
+/// Outcome of the verification of one puzzle part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+
+    /// The answer matches the expected one
+    Pass,
+
+    /// The answer differs from the expected one, or could not be computed
+    Fail (String),
+
+    /// There is no expected answer to compare with
+    Missing,
+}
+
+/// Number of [verdicts](Verdict) of each kind
+#[derive(Debug, Copy, Clone, Default)]
+pub struct VerifySummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => f.write_str("PASS"),
+            Verdict::Fail(reason) => write!(f, "FAIL ({})", reason),
+            Verdict::Missing => f.write_str("MISSING"),
+        }
+    }
+}
+
+impl VerifySummary {
+
+    /// Account for a new `verdict`
+    fn add (&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail(_) => self.failed += 1,
+            Verdict::Missing => self.missing += 1,
+        }
+    }
+
+    /// Merge the counts of `other` into this summary
+    pub fn merge (&mut self, other: &VerifySummary) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.missing += other.missing;
+    }
+}
+
+/// Solve all the days of the provided `year` module that are in `day_range`, and compare the answers
+/// with the ones stored in `answers_dir`. The puzzle inputs are read from `input`.
+///
+/// When `save_missing` is set, the answers that are not known yet are saved as the expected ones.
+pub fn verify_year<Y> (
+    year: &Y,
+    day_range: impl RangeBounds<u32>,
+    input: &InputSource,
+    answers_dir: &Path,
+    save_missing: bool) -> VerifySummary
+where Y : Year + ?Sized {
+
+    println!("=========================");
+    println!("Verification of year {:?}", year.get_year());
+
+    let mut summary = VerifySummary::default();
+    for day in (1..= 25).filter(|day| day_range.contains(day)) {
+
+        // Get the function related to the current day, or skip the test
+        let Some (fn_solve) = year.get_day_fn(day) else { continue };
+
+        let answers_path = answers_path(answers_dir, year.get_year(), day);
+        let expected = load_answers(&answers_path);
+
+        let (verdict_a, verdict_b) = match solve_day(year.get_year(), day, fn_solve, &Params::new(year.get_day_params(day)), input) {
+            Ok((a, b, _durations)) => {
+
+                if save_missing && expected.is_none() {
+                    match save_answers(&answers_path, &a, &b) {
+                        Ok(()) => println!(" {:02} - answers saved to {}", day, answers_path.display()),
+                        Err(err) => println!(" {:02} - {}", day, err),
+                    }
+                }
+
+                let expected = expected.unwrap_or_default();
+                (check(&a, expected.first()), check(&b, expected.get(1)))
+            },
+            Err(err) => {
+                let verdict = Verdict::Fail(format!("error: {}", err));
+                (verdict.clone(), verdict)
+            }
+        };
+
+        println!(" {:02} - {:<25} | A: {} | B: {}",
+                 day, year.get_day_name(day).unwrap_or_default(), verdict_a, verdict_b);
+
+        summary.add(&verdict_a);
+        summary.add(&verdict_b);
+    }
+
+    summary
+}
+
+/// Compare the `answer` with the `expected` one
+fn check (answer: &Solution, expected: Option<&String>) -> Verdict {
+    let answer = answer.to_string();
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if *expected == answer => Verdict::Pass,
+        Some(expected) => Verdict::Fail(format!("got {}, expected {}", answer, expected)),
+    }
+}
+
+/// Location of the expected answers for the given `day` of the `year`
+fn answers_path (answers_dir: &Path, year: u32, day: u32) -> PathBuf {
+    answers_dir.join(format!("{}", year)).join(format!("{:02}.txt", day))
+}
+
+/// Load the expected answers stored at `path`, if any
+fn load_answers (path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(path).ok()?;
+    Some(content.lines().map(|line| line.trim_end().to_string()).collect())
+}
+
+/// Save the answers `a` and `b` as the expected ones at `path`
+fn save_answers (path: &Path, a: &Solution, b: &Solution) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{}\n{}\n", a, b))
+        .map_err(|err| anyhow!("Failed to save answers to {}: {}", path.display(), err))
+}
@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 1000
 2000
@@ -19,6 +22,7 @@ const TEST: &str = "\
 
 type Calories = u32;
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -66,13 +70,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok (max_calories as usize)
 }
 
-pub fn day_1 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 24000);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 45000);
+pub fn day_1 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 24000);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 45000);
+    }
+}
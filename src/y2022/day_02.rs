@@ -1,12 +1,16 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 A Y
 B X
 C Z
 ";
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -59,13 +63,27 @@ fn part_b (content: &[&str]) -> anyhow::Result<usize> {
     Ok(score as usize)
 }
 
-pub fn day_2(content: &[&str]) -> anyhow::Result<(Solution, Solution)> {
-
-    debug_assert!(part_a(&split(TEST)).unwrap_or_default() == 15);
-    debug_assert!(part_b(&split(TEST)).unwrap_or_default() == 12);
+pub fn day_2(content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> anyhow::Result<(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a(&split(TEST)).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b(&split(TEST)).unwrap(), 12);
+    }
+}
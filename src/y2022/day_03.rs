@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -13,6 +16,7 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 /// Bitset: 26 first lsb for the items 'a' to 'z', and 26 more for the items 'A' to 'Z'
 type RuckSackContent = u64;
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -69,13 +73,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(priorities)
 }
 
-pub fn day_3 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 157);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 70);
+pub fn day_3 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 157);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 70);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 2-4,6-8
 2-3,4-5
@@ -27,6 +30,7 @@ impl Range {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -68,13 +72,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(count)
 }
 
-pub fn day_4(content: &[&str]) -> Result<(Solution, Solution)> {
-
-    debug_assert!(part_a(&split(TEST)).unwrap_or_default() == 2);
-    debug_assert!(part_b(&split(TEST)).unwrap_or_default() == 4);
+pub fn day_4(content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result<(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a(&split(TEST)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b(&split(TEST)).unwrap(), 4);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 ....[D]
 [N] [C]
@@ -119,6 +122,7 @@ impl Stacks {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -166,13 +170,27 @@ fn solve (content: &[&str], crane: Crane)-> Result<String> {
     Ok(stacks.get_top_row())
 }
 
-pub fn day_5 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST), Crane::CrateMover9000).unwrap_or_default() == "CMZ");
-    debug_assert!(solve (&split(TEST), Crane::CrateMover9001).unwrap_or_default() == "MCD");
+pub fn day_5 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = solve (content, Crane::CrateMover9000)?;
+    timer.lap(Phase::PartA);
     let rb = solve (content, Crane::CrateMover9001)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Text(ra), Solution::Text(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST), Crane::CrateMover9000).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST), Crane::CrateMover9001).unwrap(), "MCD");
+    }
+}
@@ -1,7 +1,11 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+#[cfg(test)]
 const TEST_2: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
 
 
@@ -43,13 +47,27 @@ fn find_marker (content: &str, marker_length: u32) -> Result<usize> {
     Err(anyhow!("Marker not found"))
 }
 
-pub fn day_6 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(find_marker (TEST, 4).unwrap_or_default() == 11);
-    debug_assert!(find_marker (TEST_2, 14).unwrap_or_default() == 19);
+pub fn day_6 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = find_marker(content [0], 4)?;
+    timer.lap(Phase::PartA);
     let rb = find_marker(content [0], 14)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(find_marker (TEST, 4).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(find_marker (TEST_2, 14).unwrap(), 19);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
 
+#[cfg(test)]
 const TEST: &str = "\
 $ cd /
 $ ls
@@ -26,6 +29,12 @@ $ ls
 5626152 d.ext
 7214296 k";
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::new("disk_size", "70000000", "Total disk space of the device"),
+    ParamSpec::new("update_size", "30000000", "Unused space needed to run the update"),
+];
+
 /// Models the different entries of the puzzle file content
 enum Entry {
     DirUp,
@@ -35,6 +44,7 @@ enum Entry {
     Ls,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -128,13 +138,13 @@ fn part_a (content: &[&str]) -> Result<usize> {
     Ok(sum as usize)
 }
 
-/// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<usize> {
+/// Solve second part of the puzzle, for a disk of `disk_size` and an update requiring `update_size` of free space
+fn part_b (content: &[&str], disk_size: u32, update_size: u32) -> Result<usize> {
 
     // Collect all the dir size (root is last) and deduce the min space to free
     let all_dirs: Vec<u32> = dir_size_it(content).collect::<Result<Vec<u32>>>()?;
     let root_size = all_dirs.last().unwrap();
-    let to_free = 30_000_000 - (70_000_000 - root_size);
+    let to_free = update_size.saturating_sub(disk_size.saturating_sub(*root_size));
 
     // Filter out all the directories that have the minimum required size and keep the smallest one
     let match_size = all_dirs
@@ -146,13 +156,28 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(*match_size as usize)
 }
 
-pub fn day_7 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 95437);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 24933642);
+pub fn day_7 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
-    let rb = part_b(content)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content, params.get("disk_size")?, params.get("update_size")?)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 95437);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST), 70_000_000, 30_000_000).unwrap(), 24933642);
+        assert_eq!(part_b (&split(TEST), 50_000_000, 30_000_000).unwrap(), 48381165);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 30373
 25512
@@ -8,6 +11,7 @@ const TEST: &str = "\
 33549
 35390";
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -162,13 +166,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(highest_score)
 }
 
-pub fn day_8 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 21);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 8);
+pub fn day_8 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 21);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 8);
+    }
+}
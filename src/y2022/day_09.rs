@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "\
 R 4
 U 4
@@ -13,6 +16,17 @@ D 1
 L 5
 R 2";
 
+#[cfg(test)]
+const TEST_2: &str = "\
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
 /// Models a move instruction
 #[derive(Debug)]
 struct Move (Direction, u8);
@@ -23,6 +37,7 @@ struct Rope<const N: usize> {
     visited: HashSet<Coo>,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -109,12 +124,31 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(rope.visited.len())
 }
 
-pub fn day_9 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 13);
+pub fn day_9 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 13);
+    }
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_part_b_2 () {
+        assert_eq!(part_b (&split(TEST_2)).unwrap(), 36);
+    }
+}
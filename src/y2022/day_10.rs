@@ -1,6 +1,11 @@
+use std::fmt::{Display, Formatter};
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+use crate::tools::read_letters;
 
+#[cfg(test)]
 const TEST: &str = "\
 addx 15
 addx -11
@@ -181,17 +186,24 @@ impl Screen {
         self.cycle += 1;
     }
 
-    fn _print(&self) {
-        for y in 0..6 {
-            println! ();
-            for x in 0..40 {
-                if self.data [y*40+x] { print!("#") }
-                else { print!(" ") }
-            }
+    /// Read the letters drawn on the screen
+    fn read(&self) -> Result<String> {
+        read_letters(40, |x, y| self.data [y*40+x])
+    }
+}
+
+/// Draw the screen, one row per line
+impl Display for Screen {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.data.chunks(40) {
+            let row: String = row.iter().map(|&lit| if lit { '#' } else { '.' }).collect();
+            writeln!(f, "{}", row)?;
         }
+        std::fmt::Result::Ok(())
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -249,8 +261,8 @@ fn part_a (content: &[&str]) -> Result<usize> {
     Ok(strengths as usize)
 }
 
-/// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<usize> {
+/// Draw the screen by executing the instructions of the puzzle file `content`
+fn draw_screen (content: &[&str]) -> Result<Screen> {
 
     let instructions = get_instructions(content);
     let mut screen = Screen::new();
@@ -267,17 +279,45 @@ fn part_b (content: &[&str]) -> Result<usize> {
         }
     }
 
-    //screen._print();
-    Ok(0)
+    Ok(screen)
 }
 
-pub fn day_10 (content: &[&str]) -> Result <(Solution, Solution)> {
+/// Solve second part of the puzzle
+fn part_b (content: &[&str]) -> Result<String> {
+    draw_screen(content)?.read()
+}
 
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 13140);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 0);
+pub fn day_10 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
-    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+    Ok((Solution::Unsigned(ra), Solution::Text(rb)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 13140);
+    }
+
+    #[test]
+    fn test_part_b () {
+        // The example does not draw letters, but a regular pattern
+        let screen = draw_screen(&split(TEST)).unwrap();
+        assert_eq!(screen.to_string(), "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+");
+        assert!(screen.read().is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 Monkey 0:
   Starting items: 79, 98
@@ -52,6 +55,7 @@ struct Monkey {
     activity_counter: usize,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -204,13 +208,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     dance (&mut monkeys, 10000, false)
 }
 
-pub fn  day_11 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 10605);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 2713310158);
+pub fn  day_11 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 10605);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 2713310158);
+    }
+}
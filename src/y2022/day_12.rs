@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo};
 
+#[cfg(test)]
 const TEST: &str = "\
 Sabqponm
 abcryxxl
@@ -104,6 +107,7 @@ impl AreaClimber {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -127,13 +131,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(steps)
 }
 
-pub fn day_12 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 31);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 29);
+pub fn day_12 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 31);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 29);
+    }
+}
@@ -2,7 +2,10 @@ use std::cmp::Ordering;
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 [1,1,3,1,1]
 [1,1,5,1,1]
@@ -55,6 +58,7 @@ struct SequenceReader<'a> {
     nesting: Nesting,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -205,13 +209,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(key_2 * key_6)
 }
 
-pub fn day_13 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 13);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 140);
+pub fn day_13 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 140);
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{find_coo_extents, Coo, IntReader};
 
+#[cfg(test)]
 const TEST: &str = "\
 498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
@@ -48,6 +51,7 @@ impl Cell for Tile {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -136,10 +140,9 @@ impl Cave {
 
         // Extract the ground coordinates and compute the size of the area
         let lines = Self::load_lines(content);
-        let (min, max) = find_coo_extents(lines.iter().flatten().copied());
+        let coos = lines.iter().flatten().copied().chain([POUR_COO]);
+        let (min, max) = find_coo_extents(coos).expect("The pouring coordinate is included");
 
-        let min = Coo { x: min.x.min (POUR_COO.x), y: min.y.min (POUR_COO.y) };
-        let max = Coo { x: max.x.max (POUR_COO.x), y: max.y.max (POUR_COO.y) };
         let width = (max.x - min.x) as usize +1;
         let height = (max.y - min.y) as usize +1;
 
@@ -201,13 +204,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(cave.sand_counter)
 }
 
-pub fn day_14 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 24);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() != 0);
+pub fn day_14 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 24);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 93);
+    }
+}
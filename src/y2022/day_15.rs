@@ -3,8 +3,11 @@ use anyhow::*;
 use itertools::Itertools;
 use num::Integer;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
 use crate::tools::{Coo, IntInterval, IntIntervals, IntReader};
 
+#[cfg(test)]
 const TEST: &str = "\
 Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
@@ -21,6 +24,11 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::new("row", "2000000", "Row where the positions that cannot contain a beacon are counted"),
+];
+
 #[derive(Debug, Copy, Clone)]
 struct Pair {
     sensor: Coo,
@@ -40,6 +48,7 @@ impl Pair {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -289,6 +298,7 @@ fn part_b (content: &[&str]) -> Result<usize> {
 /// Solve second part of the puzzle, **slowly**.
 /// The idea here just consists in testing all the 4.10^6 possible rows, one by one,
 /// the same way as in part 1. See function [part_b] for a better way.
+#[cfg(test)]
 fn part_b_slow (content: &[&str]) -> Result<usize> {
 
     let pairs = collect_device_pairs (content)?;
@@ -307,14 +317,32 @@ fn part_b_slow (content: &[&str]) -> Result<usize> {
     Err(anyhow!("no solution found"))
 }
 
-pub fn day_15 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST), 10).unwrap_or_default() == 26);
-    debug_assert!(part_b_slow (&split(TEST)).unwrap_or_default() == 56000011);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 56000011);
+pub fn day_15 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    let ra = part_a(content, 2000000)?;
+    let ra = part_a(content, params.get("row")?)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST), 10).unwrap(), 26);
+    }
+
+    #[test]
+    fn test_part_b_slow () {
+        assert_eq!(part_b_slow (&split(TEST)).unwrap(), 56000011);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 56000011);
+    }
+}
@@ -2,8 +2,11 @@ use std::cmp::Ordering;
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Solution};
-use crate::tools::{compute_all_pair_distances};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+use crate::tools::Graph;
 
+#[cfg(test)]
 const TEST: &str = "\
 Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
@@ -111,6 +114,7 @@ impl ProcessState {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -145,16 +149,17 @@ fn collect_valves<'a> (content: &'a [&'a str]) -> Result<Vec<Valve<'a>>> {
 /// gives the shortest distance when going from valve 2 to valve 7)
 fn compute_distance_matrix (valves: &[Valve]) -> DistanceMatrix {
 
-    // For some `node` index, iterates on the adjacent valves. For each of them we
-    // yield its index and the weight to get there (1 minute in this puzzle)
-    let fn_adjacency = |node: usize| {
-        valves [node].edges.iter().map (|&adj_valve| {
-            let adj_index = valves.iter().position (|valve| valve.name == adj_valve).unwrap();
-            (adj_index, 1)
-        })
-    };
+    // The valves are added first so that the node indexes match the vector ones.
+    // Moving through a tunnel takes 1 minute in this puzzle
+    let mut graph = Graph::new();
+    for valve in valves { graph.add_node(valve.name); }
+    for valve in valves {
+        for &adj_valve in valve.edges.iter() {
+            graph.add_edge(valve.name, adj_valve, 1);
+        }
+    }
 
-    compute_all_pair_distances(valves.len(), fn_adjacency)
+    graph.all_pair_distances()
 }
 
 /// Find the best sequence with a *Branch and Bound* algorithm, implemented with a DFS queue.
@@ -296,13 +301,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok (highest_pressure as usize)
 }
 
-pub fn day_16 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 1651);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 1707);
+pub fn day_16 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 1651);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 1707);
+    }
+}
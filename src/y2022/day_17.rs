@@ -1,10 +1,15 @@
-use std::collections::HashMap;
 use anyhow::*;
-use itertools::Itertools;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{find_cycle, FrameRecorder, FRAMES_PARAM};
 
+#[cfg(test)]
 const TEST: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[FRAMES_PARAM];
+
 /// Encodes a single chamber row with a Byte, each bit at 1 is occupied.
 type StackRow = u8;
 
@@ -162,23 +167,6 @@ impl Chamber {
         }
     }
 
-    /// Instantiate a new chamber from the provided chamber `state`.
-    ///
-    /// **This state retains only the last 8 rows!**
-    fn from_state (state: &ChamberState) -> Self {
-        let mut chamber = Chamber {
-            rows: Self::decode_top(state.stack_top),
-            top: 0,
-            rock_bottom: 0,
-            rock_counter: 0,
-            current_rock: state.next_rock.next().next().next().next().to_rock(),
-            next_rock: state.next_rock,
-        };
-
-        chamber.extend();
-        chamber
-    }
-
     /// Update the falling rock position by one step:
     /// 1) The rock moves left or right according to `direction`
     /// 2) The rock drops by one row. If not possible a new rock is instantiated
@@ -231,20 +219,6 @@ impl Chamber {
         }
     }
 
-    /// Decode the `encoded` top of stack and returns the 8 corresponding [StackRow]
-    fn decode_top (mut encoded: u64) -> Vec<StackRow> {
-
-        let mut v: Vec<StackRow> = (0..8).map(|_| {
-            let row = encoded & 0xff;
-            encoded >>= 8;
-
-            row as StackRow
-        }).collect();
-
-        v.reverse();
-        v
-    }
-
     /// Encode the 8 top most [StackRow] of the chamber's stack
     fn encode_top (&self) -> u64 {
 
@@ -291,38 +265,28 @@ impl Chamber {
         false
     }
 
-    /// Debug print the chamber content
-    fn _print (&self) {
-        let above_rock = self.rows.len() - self.rock_bottom - 4;
-        for (idx, row) in self.rows.iter().rev ().enumerate() {
-            let row_rock = if idx >= above_rock && idx < above_rock +4 {
-                self.current_rock.0 [idx - above_rock]
-            } else {
-                0
-            };
+    /// Record the top of the chamber, with the falling rock, with the `recorder`
+    fn record (&self, recorder: &mut FrameRecorder) {
 
-            Self::_print_row(*row, row_rock);
-            if idx > 20 {
-                println!("  (...)");
-                break;
-            }
-        }
-        println!("+-------+");
-    }
+        const VIEW_HEIGHT: usize = 40;
+        let num_rows = self.rows.len();
+        recorder.record_with(7, VIEW_HEIGHT, |x, y| {
+
+            // Rows are stored bottom up, and the frame is drawn top down
+            let Some (idx) = num_rows.checked_sub(y+1) else { return ' ' };
+            let rock_row = match idx.checked_sub(self.rock_bottom) {
+                Some (offset) if offset < 4 => self.current_rock.0 [3-offset],
+                _ => 0,
+            };
 
-    fn _print_row (row: StackRow, rock_row: StackRow) {
-        let content = (0..7).map (|idx| {
-            let mask = 0b1000000 >> idx;
-            match (row & mask, rock_row & mask) {
+            let mask = 0b1000000 >> x;
+            match (self.rows [idx] & mask, rock_row & mask) {
                 (0, 0) => '.',
                 (_, 0) => '#',
                 (0, _) => '@',
                 _ => '*'
             }
-        }).join("");
-        print!("|");
-        print!("{}", content);
-        println!("|");
+        });
     }
 }
 
@@ -355,7 +319,8 @@ fn infinite_tower_it (jet_pattern: &str) -> impl Iterator<Item=(ChamberState, u3
 
 /// Given the `jet_pattern` and a `chamber`, iterates until `num_blocks` have fallen.
 /// If not 0, parameter `jet_index` enables to start later in the jet sequence.
-fn drop_blocks (jet_pattern: &str, chamber: &mut Chamber, jet_index: usize, num_blocks: u32) -> Result<()> {
+/// Each step is recorded with the `recorder`.
+fn drop_blocks (jet_pattern: &str, chamber: &mut Chamber, jet_index: usize, num_blocks: u32, recorder: &mut FrameRecorder) -> Result<()> {
 
     for pattern in jet_pattern.as_bytes().iter().cycle().skip(jet_index) {
         match pattern {
@@ -363,6 +328,7 @@ fn drop_blocks (jet_pattern: &str, chamber: &mut Chamber, jet_index: usize, num_
             b'>' => chamber.do_step(JetDirection::Right),
             _ => bail!("Invalid character in pattern '{}'", pattern),
         };
+        chamber.record(recorder);
 
         if chamber.rock_counter >= num_blocks { break }
     }
@@ -370,12 +336,12 @@ fn drop_blocks (jet_pattern: &str, chamber: &mut Chamber, jet_index: usize, num_
     Ok(())
 }
 
-/// Solve first part of the puzzle
-fn part_a (jet_pattern: &str) -> Result<usize> {
+/// Solve first part of the puzzle, recording each step of the falling rocks with the `recorder`
+fn part_a (jet_pattern: &str, recorder: &mut FrameRecorder) -> Result<usize> {
 
     let mut chamber = Chamber::new();
 
-    drop_blocks(jet_pattern, &mut chamber, 0, 2022)?;
+    drop_blocks(jet_pattern, &mut chamber, 0, 2022, recorder)?;
     let height = chamber.get_stack_height();
 
     Ok(height)
@@ -384,53 +350,39 @@ fn part_a (jet_pattern: &str) -> Result<usize> {
 /// Solve second part of the puzzle
 fn part_b (jet_pattern: &str) -> Result<usize> {
 
-    type Index = usize;
-    type Height = u32;
-    type Info = (Index, Height);
-
-    // To collect the states we have already seen
-    let mut states = HashMap::<ChamberState, Info>::new();
+    const NUM_ROCKS: usize = 1000_000_000_000;
 
-    // Iterate as long as we encounter new states
-    for (idx, (state, height)) in infinite_tower_it(jet_pattern).enumerate() {
+    // The stack grows by the same height at each cycle of the chamber states.
+    // The heights are those of the stack when the states are reached, i.e. after `idx+1` rocks
+    let states = infinite_tower_it(jet_pattern).take(100000);
+    let (cycle, heights) = find_cycle(states).ok_or(anyhow!("No cycle found"))?;
+    Ok(cycle.extrapolate(NUM_ROCKS - 1, |idx| heights [idx] as usize))
+}
 
-        // If not a new state ...
-        if let Some ((first_idx, first_height)) = states.get(&state) {
+pub fn day_17 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-            // Do some maths to deduce the characteristics of the cycle
-            let num_before_cycle = first_idx +1;
-            let cycle_len = idx - first_idx;
-            let cycle_height = height - first_height;
-            let num_cycles = (1000_000_000_000 - num_before_cycle) / cycle_len;
-            let remaining = (1000_000_000_000 - num_before_cycle) % cycle_len;
+    let mut recorder = FrameRecorder::new(params.get("frames")?, "2022-17");
+    let ra = part_a(content [0], &mut recorder)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content [0])?;
+    timer.lap(Phase::PartB);
 
-            // Instantiate a chamber to simulate the `remaining` rocks in the last cycle
-            let mut chamber = Chamber::from_state(&state);
-            let init_height = chamber.get_stack_height();
-            drop_blocks(jet_pattern, &mut chamber, state.jet_index +1, remaining as u32)?;
-            let final_height = chamber.get_stack_height();
+    recorder.save()?;
 
-            let total_height = *first_height as usize +
-                cycle_height as usize * num_cycles +
-                final_height - init_height;
+    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
+}
 
-            return Ok(total_height);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        states.insert(state, (idx, height));
-        if idx > 100000 { break }
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (TEST, &mut FrameRecorder::default()).unwrap(), 3068);
     }
 
-    bail!("No cycle found");
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (TEST).unwrap(), 1514285714288);
+    }
 }
-
-pub fn day_17 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (TEST).unwrap_or_default() == 3068);
-    debug_assert!(part_b (TEST).unwrap_or_default() == 1514285714288);
-
-    let ra = part_a(content [0])?;
-    let rb = part_b(content [0])?;
-
-    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
@@ -1,8 +1,10 @@
-use std::collections::HashSet;
 use anyhow::*;
 use crate::{Solution};
-use crate::tools::{ArraySet, IntReader};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+use crate::tools::{find_coo_n_extents, ArraySet, Coo3, CooN, IntReader};
 
+#[cfg(test)]
 const TEST: &str = "\
 2,2,2
 1,2,2
@@ -18,16 +20,10 @@ const TEST: &str = "\
 2,1,5
 2,3,5";
 
-/// An axis
-#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug)]
-enum Axis { X, Y, Z }
-
 /// Droplet 3D coordinate
-type Droplet = (i8, i8, i8);
-
-/// Droplet surface coordinate
-type Surface = (Axis, i8, i8, i8);
+type Droplet = Coo3;
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -38,114 +34,99 @@ fn load_droplets (content: &[&str]) -> Result<Vec<Droplet>> {
     let mut reader = IntReader::new(false);
 
     content.iter().map (|&row| {
-        let mut it = reader.iter_row::<i8>(row);
-        let a = it.next().ok_or_else(|| anyhow!("No enough value in row {}", row))?;
-        let b = it.next().ok_or_else(|| anyhow!("No enough value in row {}", row))?;
-        let c = it.next().ok_or_else(|| anyhow!("No enough value in row {}", row))?;
-        Ok ((a, b, c))
+        let components: [isize; 3] = reader.process_row_fix(row)
+            .ok_or_else(|| anyhow!("Expecting 3 values in row {}", row))?;
+        Ok (Droplet::from(components))
     }).collect()
 }
 
 /// Iterate on all the `droplets` and count the number of surfaces that occur only one time.
-/// `extend` must provide the highest lava coordinate for the 3 axis.
-fn count_free_surfaces (droplets: &[Droplet], extend: (i8, i8, i8)) -> usize {
+/// The extent `(min, max)` must provide the lowest and highest lava coordinates.
+fn count_free_surfaces (droplets: &[Droplet], (min, max): (Droplet, Droplet)) -> usize {
 
     // The set of all possible surface coordinates is small. Use an array set to accelerate things.
+    // A surface is given by its axis, followed by the coordinate of the droplet on its lower side.
     let mut surface_set = ArraySet::new(
-        [0; 4],
-        [2, extend.0 as isize +1, extend.1 as isize +1, extend.2 as isize +1]
+        [0, min [0], min [1], min [2]],
+        [2, max [0] +1, max [1] +1, max [2] +1]
     );
 
-    let mut add_or_remove_surface = | surface: &Surface | {
-        let item = [surface.0 as isize, surface.1 as isize, surface.2 as isize, surface.3 as isize];
-        surface_set.toggle (&item);
-    };
-
     // For each droplet
     for droplet in droplets {
 
         // and for each possible surface
-        let surfaces = [
-            (Axis::X, droplet.0, droplet.1, droplet.2),
-            (Axis::X, droplet.0 +1, droplet.1, droplet.2),
-            (Axis::Y, droplet.0, droplet.1, droplet.2),
-            (Axis::Y, droplet.0, droplet.1 + 1, droplet.2),
-            (Axis::Z, droplet.0, droplet.1, droplet.2),
-            (Axis::Z, droplet.0, droplet.1, droplet.2 + 1),
-        ];
-
-        // Add the surface if not in the set, otherwise remove it
-        for surface in surfaces {
-            add_or_remove_surface (&surface);
+        for axis in 0..3 {
+            for lower in [*droplet, *droplet + Droplet::unit(axis)] {
+
+                // Add the surface if not in the set, otherwise remove it
+                surface_set.toggle (&[axis as isize, lower [0], lower [1], lower [2]]);
+            }
         }
     }
 
     surface_set.count ()
 }
 
-/// Flood the coordinates of air droplets around the set of lava `droplets`, knowing its maximum
-/// `extend` (highest lava coordinate for the 3 axis).
+/// Flood the coordinates of air droplets around the set of lava `droplets`, knowing its
+/// extent `(min, max)` (lowest and highest lava coordinates).
 ///
 /// If, when extending an air droplet, we bump into a lava droplet, we increase the surface by +1
-fn count_free_surface_with_flood(droplets: &HashSet<Droplet>, extend: (i8, i8, i8)) -> usize {
+fn count_free_surface_with_flood(droplets: &[Droplet], (min, max): (Droplet, Droplet)) -> usize {
 
-    // 6 moving directions around a cube
-    let directions = [(0, 0, 1), (0, 1, 0), (1, 0, 0), (0, 0, -1), (0, -1, 0), (-1, 0, 0)];
+    // The air around the lava, with a margin of 1 to flow around it
+    let (min, max) = (min - CooN([1; 3]), max + CooN([1; 3]));
 
-        // The set of all possible 3D coordinates is small. Use an array set to accelerate things.
-    let mut out_volume = ArraySet::new(
-        [-1; 3],
-        [extend.0 as isize +1, extend.1 as isize +1, extend.2 as isize +1]
-    );
+    // The set of all possible 3D coordinates is small. Use array sets to accelerate things.
+    let mut lava = ArraySet::new(min, max);
+    for droplet in droplets {
+        lava.set(droplet);
+    }
+    let mut out_volume = ArraySet::new(min, max);
 
     let mut free_surfaces = 0;
     let mut queue = Vec::<Droplet>::new();
 
     // Start we the coordinate of an air droplet and flood ...
-    queue.push((-1, -1, -1));
+    queue.push(min);
+    out_volume.set(&min);
     while let Some(air_drop) = queue.pop() {
 
         // ... in all 6 directions
-        for dir in &directions {
-            let neighbor = (air_drop.0 + dir.0, air_drop.1 + dir.1, air_drop.2 + dir.2);
+        for neighbor in air_drop.iter_adjacent_orthogonal() {
+
+            // Do not go too far
+            if !neighbor.is_inside(&min, &max) { continue }
 
             // Bump into lava
-            if droplets.contains(&neighbor) {
+            if lava.test(&neighbor) {
                 free_surfaces += 1;
                 continue
             }
 
-            // Do not go too far
-            if neighbor.0 < -1 || neighbor.1 < -1 || neighbor.2 < -1 { continue }
-            if neighbor.0 > extend.0+1 || neighbor.1 > extend.1+1 || neighbor.2 > extend.2+1 { continue }
-
             // Do not repeat ourselves
-            let neighbor_item = [neighbor.0 as isize, neighbor.1 as isize, neighbor.2 as isize];
-            if out_volume.test(&neighbor_item) { continue }
+            if out_volume.test(&neighbor) { continue }
 
             queue.push(neighbor);
-            out_volume.set(&neighbor_item);
+            out_volume.set(&neighbor);
         }
     }
 
     free_surfaces
 }
 
-/// Return the max x, y, z coordinates among all the droplets
-fn get_lava_extend (droplets: &[Droplet]) -> (i8, i8, i8) {
-
-    droplets.iter().fold ((0,0,0), |acc, droplet| {
-        (acc.0.max(droplet.0), acc.1.max(droplet.1), acc.2.max(droplet.2) )
-    })
+/// Return the lowest and highest x, y, z coordinates among all the droplets.
+/// Fails if there is no droplet.
+fn get_lava_extent (droplets: &[Droplet]) -> Result<(Droplet, Droplet)> {
+    find_coo_n_extents(droplets.iter().copied()).ok_or(anyhow!("No droplet"))
 }
 
 /// Solve first part of the puzzle
 fn part_a (_content: &[&str]) -> Result<usize> {
 
     let droplets = load_droplets(&_content)?;
-    let extend = get_lava_extend(&droplets);
+    let extent = get_lava_extent(&droplets)?;
 
-    let count = count_free_surfaces(&droplets, extend);
+    let count = count_free_surfaces(&droplets, extent);
 
     Ok(count)
 }
@@ -154,21 +135,34 @@ fn part_a (_content: &[&str]) -> Result<usize> {
 fn part_b (_content: &[&str]) -> Result<usize> {
 
     let droplets = load_droplets(&_content)?;
-    let extend = get_lava_extend(&droplets);
+    let extent = get_lava_extent(&droplets)?;
 
-    let droplets = HashSet::from_iter(droplets.into_iter());
-    let free_surface = count_free_surface_with_flood(&droplets, extend);
+    let free_surface = count_free_surface_with_flood(&droplets, extent);
 
     Ok(free_surface)
 }
 
-pub fn day_18 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 64);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 58);
+pub fn day_18 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 64);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 58);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 1
 2
@@ -20,6 +23,7 @@ struct GPS {
     zero_index: usize,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -95,30 +99,44 @@ impl GPS {
 }
 
 /// Solve first part of the puzzle
-fn part_a (content: &[&str]) -> Result<isize> {
+fn part_a (gps: &GPS) -> Result<isize> {
 
-    let gps = GPS::new(content)?;
     let decrypted = gps.decrypt_with_key(1, 1);
 
     Ok(decrypted)
 }
 
 /// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<isize> {
+fn part_b (gps: &GPS) -> Result<isize> {
 
-    let gps = GPS::new(content)?;
     let decrypted = gps.decrypt_with_key(811589153, 10);
 
     Ok(decrypted)
 }
 
-pub fn day_20 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_20 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 3);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 1623178306);
+    let gps = GPS::new(content)?;
+    timer.lap(Phase::Parse);
+    let ra = part_a(&gps)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(&gps)?;
+    timer.lap(Phase::PartB);
+
+    Ok((Solution::Signed(ra), Solution::Signed(rb)))
+}
 
-    let ra = part_a(content)?;
-    let rb = part_b(content)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok((Solution::Unsigned(ra as usize), Solution::Unsigned(rb as usize)))
-}
\ No newline at end of file
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&GPS::new(&split(TEST)).unwrap()).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&GPS::new(&split(TEST)).unwrap()).unwrap(), 1623178306);
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 root: pppw + sjmn
 dbpl: 5
@@ -20,6 +23,7 @@ drzm: hmdt - zczc
 hmdt: 32
 ";
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -280,10 +284,24 @@ fn solve (content: &[&str]) -> Result<(usize, usize)> {
     Ok((root_val, human_val))
 }
 
-pub fn day_21 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST)).unwrap_or_default() == (152, 301));
+pub fn day_21 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let (ra, rb) = solve(content)?;
+    timer.lap(Phase::BothParts);
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST)).unwrap().0, 152);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST)).unwrap().1, 301);
+    }
+}
@@ -1,7 +1,11 @@
+use std::collections::HashSet;
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "        ...#
         .#..
         #...
@@ -48,6 +52,12 @@ struct Board {
 
     /// Flat or Cube mode ?
     cube: bool,
+
+    /// Width of the cube faces, in tiles
+    face_size: isize,
+
+    /// The faces of the cube, once the net of the board is folded
+    faces: Vec<Face>,
 }
 
 impl Default for Tile {
@@ -75,9 +85,88 @@ impl Cell for Tile {
     }
 }
 
-/// Rotations modeling the jumps between the faces of a cube
-enum Transform {
-    Rot90, Rot180, RotNeg90, Rot0
+/// A vector in the 3D space of the folded cube
+type Vec3 = [isize; 3];
+
+/// Opposite of the vector `v`
+fn neg (v: Vec3) -> Vec3 { [-v[0], -v[1], -v[2]] }
+
+/// Dot product of the vectors `u` and `v`
+fn dot (u: Vec3, v: Vec3) -> isize { u[0]*v[0] + u[1]*v[1] + u[2]*v[2] }
+
+/// A face of the folded cube: its location in the net, and its orientation in space
+#[derive(Debug, Copy, Clone)]
+struct Face {
+
+    /// Face coordinate in the net (see [Board::get_face_coo])
+    coo: Coo,
+
+    /// Unit vector pointing outside the cube
+    normal: Vec3,
+
+    /// Unit vectors of the [Direction::Right] and [Direction::Down] directions of the net, on this face
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+
+    /// Unit vector of the direction `dir` of the net, on this face
+    fn direction (&self, dir: Direction) -> Vec3 {
+        match dir {
+            Direction::Right => self.right,
+            Direction::Left => neg(self.right),
+            Direction::Down => self.down,
+            Direction::Up => neg(self.down),
+        }
+    }
+
+    /// Get the face adjacent to this one in the net, in the direction `dir`, once folded along their common edge
+    fn fold (&self, coo: Coo, dir: Direction) -> Face {
+        let (normal, right, down) = match dir {
+            Direction::Right => (self.right, neg(self.normal), self.down),
+            Direction::Left => (neg(self.right), self.normal, self.down),
+            Direction::Down => (self.down, self.right, neg(self.normal)),
+            Direction::Up => (neg(self.down), self.right, self.normal),
+        };
+        Face { coo, normal, right, down }
+    }
+}
+
+/// Fold the net of a cube drawn on the `area`, whose faces are `face_size` tiles wide.
+/// Any of the 11 nets of the cube is supported, in any orientation.
+fn fold_cube (area: &GridCell<Tile>, face_size: usize) -> Result<Vec<Face>> {
+
+    let is_face = |coo: Coo| {
+        coo.x >= 0 && coo.y >= 0 &&
+        area.try_sample((coo.x * face_size as isize, coo.y * face_size as isize)).is_some_and(|&tile| tile != Tile::Void)
+    };
+
+    // The first face lies on top of the cube. Then walk through the net, folding each new face along
+    // the edge it shares with the one it is reached from
+    let first_x = (0..area.width() / face_size).find(|&x| is_face((x as isize, 0).into()))
+        .ok_or(anyhow!("No cube face on the first row"))?;
+    let mut faces = vec![Face { coo: (first_x as isize, 0).into(), normal: [0, 0, 1], right: [1, 0, 0], down: [0, 1, 0] }];
+
+    let mut idx = 0;
+    while idx < faces.len() {
+        let face = faces [idx];
+        for dir in Direction::iter() {
+            let coo = face.coo.next(dir);
+            if is_face(coo) && faces.iter().all(|f| f.coo != coo) {
+                faces.push(face.fold(coo, dir));
+            }
+        }
+        idx += 1;
+    }
+
+    // Check that the net is really one of a cube
+    let all_sides = faces.iter().map(|face| face.normal).collect::<HashSet<_>>();
+    if faces.len() != 6 || all_sides.len() != 6 {
+        bail!("The board is not the net of a cube ({} faces found)", faces.len());
+    }
+
+    Ok(faces)
 }
 
 
@@ -88,7 +177,19 @@ impl Board {
     fn new(content: &[&str], cube_mode: bool) -> Result<Board> {
 
         // Load the board content
-        let area = GridCell::new(content)?;
+        let area: GridCell<Tile> = GridCell::new(content)?;
+
+        // Get the width of the faces, knowing there are 6 of them
+        let num_tiles = area.iter_cells().filter(|(_, _, &tile)| tile != Tile::Void).count();
+        let face_size = (1..).find(|size| 6 * size * size >= num_tiles).unwrap();
+        if 6 * face_size * face_size != num_tiles && cube_mode {
+            bail!("The board cannot be folded as a cube, with {} tiles", num_tiles);
+        }
+
+        let faces = match cube_mode {
+            true => fold_cube(&area, face_size)?,
+            false => vec![],
+        };
 
         // Start direction and location
         let direction = Direction::Right;
@@ -104,6 +205,8 @@ impl Board {
             direction,
             coo,
             cube: cube_mode,
+            face_size: face_size as isize,
+            faces,
         })
     }
 
@@ -184,98 +287,58 @@ impl Board {
     /// The location and direction are left unchanged if they correspond to any cube's face.
     fn wrap_cube_coo (&self, coo: Coo, dir: Direction) -> (Coo, Direction) {
 
-        // See function 'get_face_coo' for this naming. They are all the valid face coordinates
-        const A: Coo = Coo { x: 1, y: 0 };
-        const B: Coo = Coo { x: 1, y: 1 };
-        const C: Coo = Coo { x: 1, y: 2 };
-        const D: Coo = Coo { x: 0, y: 3 };
-        const E: Coo = Coo { x: 0, y: 2 };
-        const F: Coo = Coo { x: 2, y: 0 };
-
-        // Get the face coordinate
+        // We are on a valid face, not lost in the emptiness of the manifold
         let face_coo = self.get_face_coo(coo);
-
-        // Handle the cases when this face is not a valid one (i.e. A -> F).
-        // In this case, we get a new face and some rotation to compute to map the coordinate
-        let (new_face, tr) = match (face_coo, dir) {
-            (f, Direction::Left) if f == A.next(Direction::Left) => (E, Transform::Rot180),
-            (f, Direction::Left) if f == E.next(Direction::Left) => (A, Transform::Rot180),
-
-            (f, Direction::Left) if f == B.next(Direction::Left) => (E, Transform::Rot90),
-            (f, Direction::Up)   if f == E.next(Direction::Up)   => (B, Transform::RotNeg90),
-
-            (f, Direction::Left) if f == D.next(Direction::Left) => (A, Transform::Rot90),
-            (f, Direction::Up)   if f == A.next(Direction::Up)   => (D, Transform::RotNeg90),
-
-            (f, Direction::Down) if f == D.next(Direction::Down) => (F, Transform::Rot0),
-            (f, Direction::Up)   if f == F.next(Direction::Up)   => (D, Transform::Rot0),
-
-            (f, Direction::Right) if f == D.next(Direction::Right) => (C, Transform::Rot90),
-            (f, Direction::Down)  if f == C.next(Direction::Down)  => (D, Transform::RotNeg90),
-
-            (f, Direction::Right) if f == C.next(Direction::Right) => (F, Transform::Rot180),
-            (f, Direction::Right) if f == F.next(Direction::Right) => (C, Transform::Rot180),
-
-            (f, Direction::Right) if f == B.next(Direction::Right) => (F, Transform::Rot90),
-            (f, Direction::Down)  if f == F.next(Direction::Down)  => (B, Transform::RotNeg90),
-
-            _ => return (coo, dir) // We are on a valid face, not lost in the emptiness of the manifold
-        };
-
-        // Compute the offset inside the current (and invalid) face
-        let cube_width = self.area.width() as isize / 3;
-        let off_x = (coo.x % cube_width) + if coo.x < 0 { cube_width } else { 0 };
-        let off_y = coo.y % cube_width + if coo.y < 0 { cube_width } else { 0 };
-
-        // Compute the new offset in the valid landing face
-        let (tr_off_x, tr_off_y) = match tr {
-            Transform::Rot0     => (off_x, off_y),
-            Transform::Rot90    => (off_y, cube_width-1-off_x),
-            Transform::Rot180   => (cube_width-1-off_x, cube_width-1-off_y),
-            Transform::RotNeg90 => (cube_width-1-off_y, off_x),
-        };
-
-        // Compute the new direction in the valid landing face
-        let new_dir = match tr {
-            Transform::Rot0     => dir,
-            Transform::Rot90    => dir.to_left(),
-            Transform::Rot180   => dir.flip(),
-            Transform::RotNeg90 => dir.to_right(),
-        };
-
-        // New final coordinate
+        if self.faces.iter().any(|face| face.coo == face_coo) { return (coo, dir) }
+
+        // Otherwise, get the face we come from, and the 3D location of the tile we left. With the cube
+        // centered on the origin, and its tiles 2 units wide, a tile center is at odd coordinates
+        let from = coo.next(dir.flip());
+        let from_face = self.get_face(from);
+        let size = self.face_size;
+        let (off_x, off_y) = (from.x - from_face.coo.x * size, from.y - from_face.coo.y * size);
+        let tile = [0, 1, 2].map(|i| {
+            from_face.normal [i] * size + from_face.right [i] * (2*off_x + 1 - size) + from_face.down [i] * (2*off_y + 1 - size)
+        });
+
+        // Crossing the edge, we land on the face oriented towards the moving direction, moving away from the face we left
+        let out = from_face.direction(dir);
+        let landing = [0, 1, 2].map(|i| tile [i] + out [i] - from_face.normal [i]);
+        let to_face = self.faces.iter().find(|face| face.normal == out).expect("A cube has 6 faces");
+        let new_dir = Direction::iter().find(|&d| to_face.direction(d) == neg(from_face.normal)).unwrap();
+
+        // Back to the 2D coordinates of the landing tile, on the net
         let new_coo = (
-            new_face.x * cube_width + tr_off_x,
-            new_face.y * cube_width + tr_off_y
+            to_face.coo.x * size + (dot(landing, to_face.right) + size - 1) / 2,
+            to_face.coo.y * size + (dot(landing, to_face.down) + size - 1) / 2,
         ).into();
 
         (new_coo, new_dir)
     }
 
-    /// Compute a coordinate reflecting in which face of the cube we are.
-    /// e.g.: A -> (1, 0)
+    /// Get the face of the cube containing the location `coo`
+    fn get_face (&self, coo: Coo) -> &Face {
+        let face_coo = self.get_face_coo(coo);
+        self.faces.iter().find(|face| face.coo == face_coo).expect("Location outside the cube")
+    }
+
+    /// Compute a coordinate reflecting in which face of the cube we are, each face of the net
+    /// being a square of `face_size` tiles. e.g., with the net of the example, A -> (2, 0):
     ///
     /// ```
-    ///     0   1   2
-    ///       +---+---+
-    /// 0     | A | F |
-    ///       +---+---+
-    /// 1     | B |
-    ///   +---+---+
-    /// 2 | E | C |
-    ///   +---+---+
-    /// 3 | D |
-    ///   +---+
+    ///     0   1   2   3
+    ///           +---+
+    /// 0         | A |
+    ///   +---+---+---+
+    /// 1 | B | C | D |
+    ///   +---+---+---+---+
+    /// 2         | E | F |
+    ///           +---+---+
     /// ```
     fn get_face_coo(&self, coo: Coo) -> Coo {
-
-        let cube_width = self.area.width() as isize / 3;
-        let x = coo.x / cube_width + if coo.x < 0 { -1 } else { 0 };
-        let y = coo.y / cube_width + if coo.y < 0 { -1 } else { 0 };
-        (x, y).into()
+        (coo.x.div_euclid(self.face_size), coo.y.div_euclid(self.face_size)).into()
     }
 
-
     /// Compute the password from the current position
     fn password (&self) -> usize {
         let num_dir = match self.direction {
@@ -288,6 +351,7 @@ impl Board {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -343,12 +407,59 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(board.password())
 }
 
-pub fn day_22 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 6032);
+pub fn day_22 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 6032);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 5031);
+    }
+
+    #[test]
+    fn test_fold_cube () {
+
+        // The 11 nets of the cube, one character per face
+        let nets = [
+            "X...|XXXX|X...", "X...|XXXX|.X..", "X...|XXXX|..X.", "X...|XXXX|...X", ".X..|XXXX|.X..", ".X..|XXXX|..X.",
+            "XX..|.XXX|.X..", "XX..|.XXX|..X.", "XX..|.XXX|...X", "XX..|.XX.|..XX", "XXX..|..XXX",
+        ];
+
+        for net in nets {
+            // Draw the net with faces of 2x2 tiles
+            let rows = net.split('|').flat_map(|row| {
+                let row: String = row.chars().flat_map(|c| if c == 'X' { ['.', '.'] } else { [' ', ' '] }).collect();
+                [row.clone(), row]
+            }).collect::<Vec<_>>();
+            let board = Board::new(&rows.iter().map(|row| row.as_str()).collect::<Vec<_>>(), true).unwrap();
+
+            // Leaving any tile in any direction, and coming back, must lead to the same tile
+            for (x, y, &tile) in board.area.iter_cells() {
+                if tile == Tile::Void { continue }
+                let coo: Coo = (x as isize, y as isize).into();
+                for dir in Direction::iter() {
+                    let (next, next_dir) = board.wrap_cube_coo(coo.next(dir), dir);
+                    assert_eq!(*board.area.sample(next), Tile::Empty, "net {}", net);
+                    let back = board.wrap_cube_coo(next.next(next_dir.flip()), next_dir.flip());
+                    assert_eq!(back, (coo, dir.flip()), "net {}", net);
+                }
+            }
+        }
+        assert!(Board::new(&["..  ", "..  ", "  ..", "  .."], true).is_err());
+    }
+}
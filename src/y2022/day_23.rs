@@ -1,8 +1,11 @@
 use std::cmp::PartialEq;
 use anyhow::*;
 use crate::Solution;
-use crate::tools::BitSet;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{BitSet, FrameRecorder, FRAMES_PARAM};
 
+#[cfg(test)]
 const TEST: &str = "\
 ..............
 ..............
@@ -19,10 +22,14 @@ const TEST: &str = "\
 ";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[FRAMES_PARAM];
+
 /// The four possible displacements
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum Direction {
@@ -104,13 +111,12 @@ impl PlayGround {
         self.field [0].width()
     }
 
-    fn _print (&self) {
-        for row in self.field.iter() {
-            println!();
-            for idx in (0..row.width()).rev () {
-                print!("{}", if row [idx] { '#' } else { '.' });
-            }
-        }
+    /// Record the locations of the elves with the `recorder`. The bits of the rows are stored right to left.
+    fn record (&self, recorder: &mut FrameRecorder) {
+        let width = self.width();
+        recorder.record_with(width, self.height(), |x, y| {
+            if self.field [y][width-1-x] { '#' } else { '.' }
+        });
     }
 
     /// Play one round of voting and moving
@@ -252,10 +258,11 @@ impl PlayGround {
     }
 }
 
-/// Solve both parts of the puzzle
-fn solve (content: &[&str]) -> Result<(usize, usize)> {
+/// Solve both parts of the puzzle, recording each round with the `recorder`
+fn solve (content: &[&str], recorder: &mut FrameRecorder) -> Result<(usize, usize)> {
 
     let mut playground = PlayGround::new(content)?;
+    playground.record(recorder);
 
     let mut round = 0;
     let mut empty_area = 0;
@@ -263,6 +270,7 @@ fn solve (content: &[&str]) -> Result<(usize, usize)> {
     let round_stop = loop {
         round += 1;
         let moved = playground.round();
+        playground.record(recorder);
 
         if round == 10 {
             empty_area = playground.compute_elves_area() - playground.num_elves();
@@ -273,11 +281,28 @@ fn solve (content: &[&str]) -> Result<(usize, usize)> {
     Ok((empty_area, round_stop))
 }
 
-pub fn day_23 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_23 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(solve (&split(TEST)).unwrap_or_default() == (110, 20));
+    let mut recorder = FrameRecorder::new(params.get("frames")?, "2022-23");
+    let (ra, rb) = solve(content, &mut recorder)?;
+    timer.lap(Phase::BothParts);
 
-    let (ra, rb) = solve(content)?;
+    recorder.save()?;
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST), &mut FrameRecorder::default()).unwrap().0, 110);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST), &mut FrameRecorder::default()).unwrap().1, 20);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 #.######
 #>>.<^<#
@@ -11,6 +14,7 @@ const TEST: &str = "\
 ";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -297,13 +301,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(go + back + go_again)
 }
 
-pub fn day_24 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 18);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 54);
+pub fn day_24 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 18);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 54);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::*;
 use itertools::Itertools;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 1=-0-2
 12111
@@ -19,6 +22,7 @@ const TEST: &str = "\
 ";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -101,13 +105,21 @@ fn _part_b (_content: &[&str]) -> Result<usize> {
     Ok(0)
 }
 
-pub fn day_25 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == "2=-1=0");
-    //debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 0);
+pub fn day_25 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = 0;//part_b(content)?;
 
     Ok((Solution::Text(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), "2=-1=0");
+    }
+}
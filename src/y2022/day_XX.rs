@@ -1,10 +1,12 @@
 use anyhow::*;
 use crate::{Cell, CellArea, Solution};
 
+#[cfg(test)]
 const TEST: &str = "\
 ";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -24,11 +26,23 @@ fn part_b (_content: &[&str]) -> Result<usize> {
 
 pub fn day_22 (_content: &[&str]) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 0);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 0);
-
     let ra = 0;//part_a(content)?;
     let rb = 0;//part_b(content)?;
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 0);
+    }
+}
@@ -25,6 +25,7 @@ mod day_13;
 mod day_14;
 
 use crate::{FnDay, Year};
+use crate::params::ParamSpec;
 
 pub struct Y2022;
 
@@ -81,5 +82,14 @@ impl Year for Y2022 {
             _ => None
         }
     }
-}
 
+    fn get_day_params(&self, day: u32) -> &'static [ParamSpec] {
+        match day {
+            7 => day_07::PARAMS,
+            15 => day_15::PARAMS,
+            17 => day_17::PARAMS,
+            23 => day_23::PARAMS,
+            _ => &[],
+        }
+    }
+}
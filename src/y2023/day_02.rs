@@ -1,14 +1,9 @@
-/*
-use std::fmt::Error;
 use anyhow::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use code_timing_macros::time_snippet;
-use const_format::concatcp;
-
-const DAY: &str = "02";
-const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
+use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -17,8 +12,12 @@ Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
 ";
 
-// ================================================================================================
+#[cfg(test)]
+fn split (content: &str) -> Vec<&str> {
+    content.lines().collect()
+}
 
+/// Extract the number of red, green and blue cubes revealed in a single `game` (e.g. `3 blue, 4 red`)
 fn extract_num_cubes (game: &str) -> Option<(usize, usize, usize)> {
 
     let cube_it = game.trim().split(',');
@@ -44,30 +43,29 @@ fn extract_num_cubes (game: &str) -> Option<(usize, usize, usize)> {
     Some ((r, g, b))
 }
 
+/// Extract the game number from the `head` of a row (e.g. `Game 12`)
 fn get_game_number_from_head (head: &str) -> Option<usize> {
     let mut it = head.trim().split(' ');
     it.next()?;
     it.next()?.parse::<usize>().ok()
 }
 
-fn part1<R: BufRead>(reader: R) -> Result<usize> {
+/// Split a `row` into its head and the list of games
+fn split_row (row: &str) -> Result<(&str, &str)> {
+    row.split_once(':').ok_or(anyhow!("invalid line: {}", row))
+}
+
+/// Solve first part of the puzzle
+fn part_a (content: &[&str]) -> Result<usize> {
 
     const MAX_RED: usize = 12;
     const MAX_GREEN: usize = 13;
     const MAX_BLUE: usize = 14;
 
     let mut game_sum = 0;
-    let lines_it = reader.lines().flatten();
-    for line in lines_it {
-
-        let (head, games) = {
-            let mut it = line.split(':');
-            (
-                it.next().ok_or(anyhow!("invalid line"))?,
-                it.next().ok_or(anyhow!("invalid line"))?,
-            )
-        };
+    for row in content {
 
+        let (head, games) = split_row(row)?;
         let game_number: usize = get_game_number_from_head(head).ok_or(anyhow!("invalid head"))?;
 
         let mut game_it = games.trim().split(';');
@@ -82,30 +80,23 @@ fn part1<R: BufRead>(reader: R) -> Result<usize> {
             }
         });
 
-        if let Some (possible) = possible {
-            if possible { game_sum += game_number; }
-        } else {
-            bail!("invalid game number");
+        match possible {
+            Some (true) => game_sum += game_number,
+            Some (false) => {},
+            None => bail!("invalid game number"),
         }
     }
     Ok(game_sum)
 }
 
-fn part2<R: BufRead>(reader: R) -> Result<usize> {
+/// Solve second part of the puzzle
+fn part_b (content: &[&str]) -> Result<usize> {
 
     let mut game_sum = 0;
-    let lines_it = reader.lines().flatten();
-    for line in lines_it {
-
-        let (_head, games) = {
-            let mut it = line.split(':');
-            (
-                it.next().ok_or(anyhow!("invalid line"))?,
-                it.next().ok_or(anyhow!("invalid line"))?,
-            )
-        };
+    for row in content {
 
-        let mut game_it = games.trim().split(';');
+        let (_head, games) = split_row(row)?;
+        let game_it = games.trim().split(';');
 
         let (min_r, min_g, min_b) = game_it.fold(
             (0, 0, 0),
@@ -126,25 +117,27 @@ fn part2<R: BufRead>(reader: R) -> Result<usize> {
     Ok(game_sum)
 }
 
-// ================================================================================================
-
-pub fn day_2() -> Result<()> {
+pub fn day_2 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    println!("=== Part 1 ===");
-    assert_eq!(8, part1(BufReader::new(TEST.as_bytes()))?);
+    let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
-    let input_file = BufReader::new(File::open(INPUT_FILE)?);
-    let result = time_snippet!(part1(input_file)?);
-    println!("Result for part 1 = {}", result);
+    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
+}
 
-    println!("\n=== Part 2 ===");
-    assert_eq!(2286, part2(BufReader::new(TEST.as_bytes()))?);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let input_file = BufReader::new(File::open(INPUT_FILE)?);
-    let result = time_snippet!(part2(input_file)?);
-    println!("Result for part 2 = {}", result);
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 8);
+    }
 
-    Ok(())
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 2286);
+    }
 }
-
- */
\ No newline at end of file
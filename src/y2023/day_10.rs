@@ -1,8 +1,11 @@
 use std::collections::{HashMap};
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "\
 7-F7-
 .FJ|7
@@ -11,6 +14,7 @@ SJLL7
 LJ.LJ
 ";
 
+#[cfg(test)]
 const TEST_2: &str = "\
 ...........
 .S-------7.
@@ -23,6 +27,7 @@ const TEST_2: &str = "\
 ...........
 ";
 
+#[cfg(test)]
 const TEST_3: &str = "\
 .F----7F7F7F7F-7....
 .|F--7||||||||FJ....
@@ -37,6 +42,7 @@ L--J.L7...LJS7F-7L7.
 ";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -274,13 +280,30 @@ fn solve (content: &[&str]) -> Result<(usize, usize)> {
     bail!("No loop found");
 }
 
-pub fn day_10 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST)).unwrap_or_default().0 == 8);
-    debug_assert!(solve (&split(TEST_2)).unwrap_or_default().1 == 4);
-    debug_assert!(solve (&split(TEST_3)).unwrap_or_default().1 == 8);
+pub fn day_10 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let (ra, rb) = solve(content)?;
+    timer.lap(Phase::BothParts);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST)).unwrap().0, 8);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST_2)).unwrap().1, 4);
+    }
+
+    #[test]
+    fn test_part_b_2 () {
+        assert_eq!(solve (&split(TEST_3)).unwrap().1, 8);
+    }
+}
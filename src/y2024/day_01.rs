@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 3   4
 4   3
@@ -12,6 +15,7 @@ const TEST: &str = "\
 3   3
 ";
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -36,9 +40,10 @@ fn make_two_lists (content: &[&str]) -> Result<(Vec<usize>, Vec<usize>)> {
 }
 
 /// Solve first part of the puzzle
-fn part_a (content: &[&str]) -> Result<usize> {
+fn part_a (v_left: &[usize], v_right: &[usize]) -> Result<usize> {
 
-    let (mut v_left, mut v_right) = make_two_lists(content)?;
+    let mut v_left = v_left.to_vec();
+    let mut v_right = v_right.to_vec();
     v_left.sort_unstable();
     v_right.sort_unstable();
 
@@ -49,9 +54,7 @@ fn part_a (content: &[&str]) -> Result<usize> {
 }
 
 /// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<usize> {
-
-    let (v_left, v_right) = make_two_lists(content)?;
+fn part_b (v_left: &[usize], v_right: &[usize]) -> Result<usize> {
 
     // Count number of occurrences
     let mut map_count: HashMap<usize, usize> = Default::default();
@@ -59,7 +62,7 @@ fn part_b (content: &[&str]) -> Result<usize> {
         map_count.entry(*v).and_modify(|v| *v += 1).or_insert(1);
     }
 
-    let similarity: usize = v_left.into_iter().map(|v| {
+    let similarity: usize = v_left.iter().map(|&v| {
         let num_occurrences = map_count.get(&v).copied().unwrap_or_default();
         v * num_occurrences
     }).sum();
@@ -67,13 +70,31 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(similarity)
 }
 
-pub fn day_1 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 11);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 31);
+pub fn day_1 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    let ra = part_a(content)?;
-    let rb = part_b(content)?;
+    let (v_left, v_right) = make_two_lists(content)?;
+    timer.lap(Phase::Parse);
+    let ra = part_a(&v_left, &v_right)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(&v_left, &v_right)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        let (v_left, v_right) = make_two_lists(&split(TEST)).unwrap();
+        assert_eq!(part_a (&v_left, &v_right).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_part_b () {
+        let (v_left, v_right) = make_two_lists(&split(TEST)).unwrap();
+        assert_eq!(part_b (&v_left, &v_right).unwrap(), 31);
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::{HashMap, HashSet};
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 47|53
 97|13
@@ -40,6 +43,7 @@ type Page = u32;
 /// A sequence of page to update
 type Update = Vec<Page>;
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -223,13 +227,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(sum as usize)
 }
 
-pub fn day_5 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 143);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 123);
+pub fn day_5 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 143);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 123);
+    }
+}
@@ -2,8 +2,11 @@ use std::fmt::Display;
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Cell, GridCell, Solution};
-use crate::tools::{Coo, Direction};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{Coo, Direction, FrameRecorder, FRAMES_PARAM};
 
+#[cfg(test)]
 const TEST: &str = "\
 ....#.....
 .........#
@@ -17,10 +20,14 @@ const TEST: &str = "\
 ......#...
 ";
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[FRAMES_PARAM];
+
 /// The state of a lab location
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum LabCell {
@@ -88,19 +95,7 @@ impl Display for Lab {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
         for y in 0..self.area.height (){
-            let row: String = (0..self.area.width ()).map(|x| {
-                let cell = self.area.sample((x, y)).to_char();
-
-                let h = &self.sample_history((x, y));
-                let vertical =  h.up || h.down;
-                let horizontal = h.left || h.right;
-                match (vertical, horizontal) {
-                    (true, true) => '+',
-                    (false, false) => cell,
-                    (true, false) => '|',
-                    (false, true) => '-',
-                }
-            }).join("");
+            let row: String = (0..self.area.width ()).map(|x| self.to_char(x, y)).join("");
 
             f.write_str("\n")?;
             f.write_str(&row)?;
@@ -213,6 +208,21 @@ impl Lab {
         })
     }
 
+    /// Character drawing the location (`x`, `y`), with the path of the guard
+    fn to_char (&self, x: usize, y: usize) -> char {
+        let cell = self.area.sample((x, y)).to_char();
+
+        let h = &self.sample_history((x, y));
+        let vertical =  h.up || h.down;
+        let horizontal = h.left || h.right;
+        match (vertical, horizontal) {
+            (true, true) => '+',
+            (false, false) => cell,
+            (true, false) => '|',
+            (false, true) => '-',
+        }
+    }
+
     fn sample_history (&self, coo: (usize, usize)) -> History {
         self.visited[coo.1 * self.area.width () + coo.0]
     }
@@ -244,23 +254,22 @@ fn will_loop (lab: &mut Lab) -> bool {
     }
 }
 
-/// Solve first part of the puzzle
-fn part_a (content: &[&str]) -> Result<usize> {
+/// Solve first part of the puzzle, recording each step of the guard with the `recorder`
+fn part_a (initial_lab: &Lab, recorder: &mut FrameRecorder) -> Result<usize> {
 
-    let mut lab = Lab::new(content)?;
+    let mut lab = initial_lab.clone();
     while lab.move_guard() {
         if let Some ((coo, dir)) = lab.get_guard_position() {
             lab.mark_visited(coo, dir);
         }
+        recorder.record_with(lab.area.width(), lab.area.height(), |x, y| lab.to_char(x, y));
     };
 
     Ok(lab.count_visited())
 }
 
 /// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<usize> {
-
-    let initial_lab = Lab::new(content)?;
+fn part_b (initial_lab: &Lab) -> Result<usize> {
 
     // Initial solving to get the guard history
     let mut lab = initial_lab.clone();
@@ -282,13 +291,32 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok (counter)
 }
 
-pub fn day_6 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_6 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 41);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 6);
+    let mut recorder = FrameRecorder::new(params.get("frames")?, "2024-06");
+    let lab = Lab::new(content)?;
+    timer.lap(Phase::Parse);
+    let ra = part_a(&lab, &mut recorder)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(&lab)?;
+    timer.lap(Phase::PartB);
 
-    let ra = part_a(content)?;
-    let rb = part_b(content)?;
+    recorder.save()?;
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&Lab::new(&split(TEST)).unwrap(), &mut FrameRecorder::default()).unwrap(), 41);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&Lab::new(&split(TEST)).unwrap()).unwrap(), 6);
+    }
+}
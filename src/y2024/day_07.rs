@@ -1,6 +1,9 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 190: 10 19
 3267: 81 40 27
@@ -27,6 +30,7 @@ enum Operator {
 /// A value to match and a list of operands (but not the operators)
 type Equation = (Value, Operands);
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -124,13 +128,27 @@ fn solve(content: &[&str], allow_concat: bool) -> Result<usize> {
     Ok(sum_valid)
 }
 
-pub fn day_7 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve(&split(TEST), false).unwrap_or_default() == 3749);
-    debug_assert!(solve(&split(TEST), true).unwrap_or_default() == 11387);
+pub fn day_7 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = solve(content, false)?;
+    timer.lap(Phase::PartA);
     let rb = solve(content, true)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve(&split(TEST), false).unwrap(), 3749);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve(&split(TEST), true).unwrap(), 11387);
+    }
+}
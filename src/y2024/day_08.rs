@@ -4,8 +4,11 @@ use anyhow::*;
 use itertools;
 use itertools::Itertools;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::Coo;
 
+#[cfg(test)]
 const TEST: &str = "\
 ............
 ........0...
@@ -153,6 +156,7 @@ impl Map {
 
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -173,13 +177,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok (count_antinodes)
 }
 
-pub fn day_8 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 14);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 34);
+pub fn day_8 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 14);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 34);
+    }
+}
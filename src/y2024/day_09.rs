@@ -2,7 +2,10 @@ use std::io;
 use std::io::Write;
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 2333133121414131402";
 
@@ -187,13 +190,27 @@ fn part_b (content: &str) -> Result<usize> {
     Ok (checksum(&fs))
 }
 
-pub fn day_9 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&TEST).unwrap_or_default() == 1928);
-    debug_assert!(part_b (&TEST).unwrap_or_default() == 2858);
+pub fn day_9 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content[0])?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content [0])?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&TEST).unwrap(), 1928);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&TEST).unwrap(), 2858);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "\
 89010123
 78121874
@@ -138,17 +141,32 @@ fn solve (map: &[&str], with_rating: bool) -> Result<usize> {
     Ok (sum_score as usize)
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
 
-pub fn day_10 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split (TEST), false).unwrap_or_default() == 36);
-    debug_assert!(solve (&split (TEST), true).unwrap_or_default() == 81);
+pub fn day_10 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = solve(content, false)?;
+    timer.lap(Phase::PartA);
     let rb = solve(content, true)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split (TEST), false).unwrap(), 36);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split (TEST), true).unwrap(), 81);
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::{HashMap};
 use anyhow::*;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "125 17";
 
 /// Return the number of digits in a number (in base 10)
@@ -106,12 +109,22 @@ fn solve (row: &str, num_blinks: u8) -> Result<usize> {
     Ok(length)
 }
 
-pub fn day_11 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (TEST, 25).unwrap_or_default() == 55312);
+pub fn day_11 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = solve(content [0], 25)?;
+    timer.lap(Phase::PartA);
     let rb = solve(content [0], 75)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (TEST, 25).unwrap(), 55312);
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "\
 RRRRIICCFF
 RRRRIICCCF
@@ -15,12 +18,14 @@ MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
 
+#[cfg(test)]
 const TEST_2: &str = "\
 AAAA
 BBCD
 BBCC
 EEEC";
 
+#[cfg(test)]
 const TEST_3: &str = "\
 EEEEE
 EXXXX
@@ -28,6 +33,7 @@ EEEEE
 EXXXX
 EEEEE";
 
+#[cfg(test)]
 const TEST_4: &str = "\
 AAAAAA
 AAABBA
@@ -37,6 +43,7 @@ ABBAAA
 AAAAAA";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -201,14 +208,40 @@ fn solve (content: &[&str]) -> Result<(usize, usize)> {
 }
 
 
-pub fn day_12 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST)).unwrap_or_default() == (1930, 1206));
-    debug_assert!(solve (&split(TEST_2)).unwrap_or_default().1 == 80);
-    debug_assert!(solve (&split(TEST_3)).unwrap_or_default().1 == 236);
-    debug_assert!(solve (&split(TEST_4)).unwrap_or_default().1 == 368);
+pub fn day_12 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let (ra, rb) = solve(content)?;
+    timer.lap(Phase::BothParts);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST)).unwrap().0, 1930);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST)).unwrap().1, 1206);
+    }
+
+    #[test]
+    fn test_part_b_2 () {
+        assert_eq!(solve (&split(TEST_2)).unwrap().1, 80);
+    }
+
+    #[test]
+    fn test_part_b_3 () {
+        assert_eq!(solve (&split(TEST_3)).unwrap().1, 236);
+    }
+
+    #[test]
+    fn test_part_b_4 () {
+        assert_eq!(solve (&split(TEST_4)).unwrap().1, 368);
+    }
+}
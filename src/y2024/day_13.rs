@@ -1,7 +1,10 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 Button A: X+94, Y+34
 Button B: X+22, Y+67
@@ -20,6 +23,7 @@ Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -120,12 +124,22 @@ fn solve (content: &[&str], with_correction: bool) -> Result<usize> {
 }
 
 
-pub fn day_13 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve(&split(TEST), false).unwrap_or_default() == 480);
+pub fn day_13 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = solve(content, false)?;
+    timer.lap(Phase::PartA);
     let rb = solve(content, true)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve(&split(TEST), false).unwrap(), 480);
+    }
+}
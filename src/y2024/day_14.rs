@@ -3,8 +3,11 @@ use std::fmt::Display;
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Solution};
-use crate::tools::{Coo, IntReader};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{Coo, FrameRecorder, IntReader, FRAMES_PARAM};
 
+#[cfg(test)]
 const TEST: &str = "\
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -20,6 +23,13 @@ p=2,4 v=2,-3
 p=9,5 v=-3,-3
 ";
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::new("width", "101", "Width of the area the robots move in"),
+    ParamSpec::new("height", "103", "Height of the area the robots move in"),
+    FRAMES_PARAM,
+];
+
 /// Model a robot position and velocity
 #[derive(Debug, Copy, Clone)]
 struct Robot {
@@ -37,6 +47,7 @@ struct Bathroom {
     swarm: Vec<Robot>,
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -85,11 +96,23 @@ impl Robot {
 impl Bathroom {
 
     /// New bathroom instance, based on the puzzle file `content`.
-    /// Parameters `area_width` and `area_height` specify the dimensions of the area
+    /// Parameters `area_width` and `area_height` specify the dimensions of the area. They must be odd,
+    /// for the area to split into quadrants, and all the robots must start inside the area.
     fn new (area_width: usize, area_height: usize, content: &[&str]) -> Result<Self> {
+
+        if area_width.is_multiple_of(2) || area_height.is_multiple_of(2) {
+            bail!("The dimensions of the area must be odd, got {}x{}", area_width, area_height);
+        }
+
+        let swarm = Self::collect_robots(content)?;
+        let inside = |x: isize, n: usize| x >= 0 && x < n as isize;
+        if let Some (robot) = swarm.iter().find(|robot| !inside(robot.pos.x, area_width) || !inside(robot.pos.y, area_height)) {
+            bail!("Robot at {},{} outside of the {}x{} area", robot.pos.x, robot.pos.y, area_width, area_height);
+        }
+
         Ok(Bathroom {
             size: (area_width, area_height),
-            swarm: Self::collect_robots(content)?,
+            swarm,
         })
     }
 
@@ -109,6 +132,18 @@ impl Bathroom {
         }).collect()
     }
 
+    /// Record the location of the swarm with the `recorder`
+    fn record (&self, recorder: &mut FrameRecorder) {
+
+        if !recorder.is_recording() { return }
+        let positions: HashSet<Coo> = HashSet::from_iter(
+            self.swarm.iter().map(|robot | robot.pos)
+        );
+        recorder.record_with(self.size.0, self.size.1, |x, y| {
+            if positions.contains(&(x, y).into ()) { '#' } else { '.' }
+        });
+    }
+
     /// Update the position of the swarm, given a number of steps `num_steps`
     fn update (&mut self, num_steps: isize) {
         for robot in self.swarm.iter_mut() {
@@ -124,50 +159,45 @@ impl Bathroom {
     /// * Setting a trigger value as low as 4 enables to find it without any false positive
     ///
     /// **This means that this technique proves to be quite general and highly effective!**
-    fn find_christmas_tree_accurate(&mut self, display_it: bool) -> usize{
+    ///
+    /// Each step is recorded with the `recorder`. The positions of the robots repeat after
+    /// `width * height` steps: the function fails if no tree is found until then.
+    fn find_christmas_tree_accurate(&mut self, recorder: &mut FrameRecorder) -> Result<usize> {
 
-        let mut steps = 0;
-        loop {
-            steps += 1;
+        for steps in 1..=self.size.0 * self.size.1 {
             self.update(1);
+            self.record(recorder);
 
             let score_per_robot = self.compute_density_factor () / self.swarm.len();
             if score_per_robot >= 10 {
-                if display_it {
-                    println!("After {} steps:", steps);
-                    println!("Score {}:", score_per_robot);
-                    println!("Safety factor {}", self.compute_safety_factor());
-                    println!("{}", self);
-                }
-                break steps;
+                return Ok(steps);
             }
         }
+        bail!("No tree found")
     }
 
     /// Second way to find the Christmas tree, using the safety score computed in part 1)
     /// as a clue. When the robots group together to form the tree, the safety factor drops
     /// drastically because they are not scattered around as before. As such,
     /// some quadrants become almost empty.
-    fn find_christmas_tree_fast(&mut self, display_it: bool) -> usize{
+    ///
+    /// Each step is recorded with the `recorder`. The positions of the robots repeat after
+    /// `width * height` steps: the function fails if no tree is found until then.
+    fn find_christmas_tree_fast(&mut self, recorder: &mut FrameRecorder) -> Result<usize> {
 
         let base_safety_factor = self.compute_safety_factor();
         let threshold = (base_safety_factor as f32 * 0.45) as usize;
-        let mut steps = 0;
 
-        loop {
-            steps += 1;
+        for steps in 1..=self.size.0 * self.size.1 {
             self.update(1);
+            self.record(recorder);
             let safety_factor = self.compute_safety_factor();
 
             if safety_factor < threshold {
-                if display_it {
-                    println!("After {} steps:", steps);
-                    println!("Safety factor {}", self.compute_safety_factor());
-                    println!("{}", self);
-                }
-                break steps;
+                return Ok(steps);
             }
         }
+        bail!("No tree found")
     }
 
     /// Compute a score that relates how close are the robots from each others.
@@ -238,28 +268,56 @@ fn part_a (content: &[&str], area_width: usize, area_height: usize) -> Result<us
     Ok(safety_factor)
 }
 
-/// Solve second part of the puzzle
-fn part_b (content: &[&str], area_width: usize, area_height: usize) -> Result<usize> {
+/// Solve second part of the puzzle, recording each step of the robots with the `recorder`
+fn part_b (content: &[&str], area_width: usize, area_height: usize, recorder: &mut FrameRecorder) -> Result<usize> {
 
     let mut bathroom = Bathroom::new(area_width, area_height, &content)?;
+    bathroom.record(recorder);
 
-    static DISPLAY_IT: bool = false;
     static METHOD_FAST_BUT_LESS_ACCURATE: bool = true;
 
     let num_steps = match METHOD_FAST_BUT_LESS_ACCURATE {
-       true =>  bathroom.find_christmas_tree_fast(DISPLAY_IT),
-       false => bathroom.find_christmas_tree_accurate(DISPLAY_IT),
+       true =>  bathroom.find_christmas_tree_fast(recorder)?,
+       false => bathroom.find_christmas_tree_accurate(recorder)?,
     } ;
 
     Ok(num_steps)
 }
 
-pub fn day_14 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_14 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST), 11, 7).unwrap_or_default() == 12);
+    let (area_width, area_height) = (params.get("width")?, params.get("height")?);
+    let mut recorder = FrameRecorder::new(params.get("frames")?, "2024-14");
+    let ra = part_a(content, area_width, area_height)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content, area_width, area_height, &mut recorder)?;
+    timer.lap(Phase::PartB);
 
-    let ra = part_a(content, 101, 103)?;
-    let rb = part_b(content, 101, 103)?;
+    recorder.save()?;
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST), 11, 7).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_part_b () {
+        let result = part_b (&split(TEST), 11, 7, &mut FrameRecorder::default());
+        assert_eq!(result.unwrap_err().to_string(), "No tree found");
+    }
+
+    #[test]
+    fn test_area_dimensions () {
+        assert!(part_a (&split(TEST), 11, 7).is_ok());
+        assert!(part_a (&split(TEST), 12, 7).is_err());
+        assert!(part_a (&split(TEST), 11, 0).is_err());
+        assert_eq!(part_a (&split(TEST), 9, 7).unwrap_err().to_string(), "Robot at 10,3 outside of the 9x7 area");
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
-use crate::tools::{Coo, Direction};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{Coo, Direction, FrameRecorder, FRAMES_PARAM};
 
+#[cfg(test)]
 const TEST: &str = "\
 ########
 #..O.O.#
@@ -15,6 +18,7 @@ const TEST: &str = "\
 
 <^^>>>vv<v>>v<<";
 
+#[cfg(test)]
 const TEST_2: &str = "\
 ##########
 #..O..O.O#
@@ -39,10 +43,14 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[FRAMES_PARAM];
+
 /// The different possible tiles in the [Warehouse] or [WarehouseWide]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum WarehouseTile {
@@ -366,8 +374,8 @@ fn load_instructions (content: &[&str]) -> Result<Vec<Direction>> {
     instructions.ok_or(anyhow!("Could not parse instructions"))
 }
 
-/// Solve first part of the puzzle
-fn part_a (content: &[&str]) -> Result<usize> {
+/// Solve first part of the puzzle, recording each move of the robot with the `recorder`
+fn part_a (content: &[&str], recorder: &mut FrameRecorder) -> Result<usize> {
 
     // Load the warehouse and the instructions from the file content
     let mut warehouse = Warehouse::new(content)?;
@@ -375,15 +383,17 @@ fn part_a (content: &[&str]) -> Result<usize> {
     let instructions = load_instructions(&content [first_instruction_line..])?;
 
     // Execute the instructions
+    recorder.record(&warehouse.area);
     for ins in instructions.iter() {
         warehouse.move_robot(*ins);
+        recorder.record(&warehouse.area);
     }
 
     Ok(warehouse.location_sum())
 }
 
-/// Solve second part of the puzzle
-fn part_b (content: &[&str]) -> Result<usize> {
+/// Solve second part of the puzzle, recording each move of the robot with the `recorder`
+fn part_b (content: &[&str], recorder: &mut FrameRecorder) -> Result<usize> {
 
     // Load the wide warehouse
     let mut warehouse = Warehouse::new(content)?.twice_wide();
@@ -393,21 +403,46 @@ fn part_b (content: &[&str]) -> Result<usize> {
     let instructions = load_instructions(&content [first_instruction_line..])?;
 
     // and execute them
+    recorder.record(&warehouse.area);
     for ins in instructions.iter() {
         warehouse.move_robot(*ins);
+        recorder.record(&warehouse.area);
     }
 
     Ok(warehouse.location_sum())
 }
 
-pub fn day_15 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_15 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 2028);
-    debug_assert!(part_a (&split(TEST_2)).unwrap_or_default() == 10092);
-    debug_assert!(part_b (&split(TEST_2)).unwrap_or_default() == 9021);
+    let mut recorder_a = FrameRecorder::new(params.get("frames")?, "2024-15-a");
+    let mut recorder_b = FrameRecorder::new(params.get("frames")?, "2024-15-b");
+    let ra = part_a(content, &mut recorder_a)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content, &mut recorder_b)?;
+    timer.lap(Phase::PartB);
 
-    let ra = part_a(content)?;
-    let rb = part_b(content)?;
+    recorder_a.save()?;
+    recorder_b.save()?;
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST), &mut FrameRecorder::default()).unwrap(), 2028);
+    }
+
+    #[test]
+    fn test_part_a_2 () {
+        assert_eq!(part_a (&split(TEST_2), &mut FrameRecorder::default()).unwrap(), 10092);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST_2), &mut FrameRecorder::default()).unwrap(), 9021);
+    }
+}
@@ -1,9 +1,11 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashSet;
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
-use crate::tools::{Coo, Direction};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+use crate::tools::{Coo, Direction, Search};
 
+#[cfg(test)]
 const TEST: &str = "\
 ###############
 #.......#....E#
@@ -27,24 +29,6 @@ type Score = usize;
 /// Location in maze: coordinate + direction
 type Location = (Coo, Direction);
 
-/// All the possible ancestor locations at some point on an optimal path
-/// (for part 2 we can have multiple ones)
-type Ancestors = Vec<Location>;
-
-/// All the visited locations with their score and ancestors
-type History = HashMap<Location, (Score, Ancestors)>;
-
-/// A location to explore, with its score and its path ancestor
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-struct Explore {
-    loc: Location,
-    score: Score,
-    previous: Option<Location>,
-}
-
-/// Dijkstra priority queue, made of sorted [locations to explore](Explore)
-type PriorityQueue = BinaryHeap<Explore>;
-
 /// Models the different possible tiles in the [Maze]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum MazeTile {
@@ -60,20 +44,6 @@ struct Maze {
     start: Location,
 }
 
-/// Implements an ordering for the [priority queue](PriorityQueue)
-impl Ord for Explore {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
-    }
-}
-
-/// Implements an ordering for the [priority queue](PriorityQueue)
-impl PartialOrd for Explore {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 impl Default for MazeTile {
     fn default() -> Self {
         MazeTile::Empty
@@ -102,6 +72,7 @@ impl Cell for MazeTile {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -125,169 +96,76 @@ impl Maze {
         })
     }
 
-    /// Given the `arrival` location and the history of `visited` locations, count
-    /// the number of coordinates that are on one of the multiple best paths.
-    fn count_best_paths_locations (arrival: Location, visited: &History) -> usize {
-
-        let mut spots: HashSet<Coo> = HashSet::new ();
-        let mut queue:Vec<Location> = vec![];
-
-        // Init the queue with the arrival location and all its possible ancestors
-        spots.insert(arrival.0);
-        for dir in Direction::iter() {
-            let loc = (arrival.0, dir);
-            if let Some ((_score, ancestors)) = visited.get(&loc) {
-                for ancestor in ancestors { queue.push(*ancestor); }
-            }
-        }
-
-        // For each location in the queue
-        while let Some(loc) = queue.pop() {
-
-            // Record the coordinate
-            spots.insert(loc.0);
-
-            // Insert all the possible predecessors
-            if let Some ((_score, ancestors)) = visited.get(&loc) {
-                for ancestor in ancestors {
-                    queue.push(*ancestor);
-                }
-            }
-        }
-
-        spots.len()
-    }
-
-    /// Update the `history` of visited locations with a new exploration element `explore`.
-    /// Return true if we see this element for the first time.
-    fn update_history (history: &mut History, explore: &Explore) -> bool {
-
-        // If this location has already been visited...
-        if let Some ((score, ancestors)) = history.get_mut(&explore.loc) {
-
-            // ... keep track of the ancestor if the score is equal
-            if explore.score == *score {
-                ancestors.push(explore.previous.unwrap());
-            }
-            false
-        }
-        // Otherwise, add this element to the history
-        else {
-            let ancestors = match explore.previous {
-                None => vec![],
-                Some(loc) => vec![loc],
-            };
-            history.insert(explore.loc, (explore.score, ancestors));
-            true
-        }
-    }
-
-    /// Given the current element `explore`, schedule to visit the location in front (if it is not a wall).
-    fn explore_ahead (&self, explore: &Explore, pq: &mut PriorityQueue) {
-
-        let current_dir = explore.loc.1;
-        let next_coo = explore.loc.0.next(current_dir);
-        let next_loc = (next_coo, current_dir);
-        let next_score = explore.score+1;
-
-        // If not a wall, program its exploration
-        if let Some (tile) = self.tiles.try_sample(next_coo) {
-            if *tile != MazeTile::Wall {
-
-                let to_explore = Explore {
-                    loc: next_loc,
-                    score: next_score,
-                    previous: Some(explore.loc)
-                };
-                pq.push(to_explore);
-            }
-        }
-    }
-
-    /// Given the current element `explore`, schedule to visit the two 90° rotations
-    fn explore_turns  (&self, explore: &Explore, pq: &mut PriorityQueue) {
-
-        let current_dir = explore.loc.1;
+    /// Locations that can be reached from the location `loc`, along with the score of the move:
+    /// one step ahead if it is not a wall, or one of the two 90° rotations
+    fn next_locations (&self, loc: Location) -> impl Iterator<Item = (Location, Score)> + '_ {
 
-        for dir in Direction::iter() {
-            if dir == current_dir || dir == current_dir.flip() { continue }
+        let (coo, current_dir) = loc;
+        let next_coo = coo.next(current_dir);
+        let ahead = match self.tiles.try_sample(next_coo) {
+            Some (tile) if *tile != MazeTile::Wall => Some (((next_coo, current_dir), 1)),
+            _ => None,
+        };
 
-            let next_loc = (explore.loc.0, dir);
-            let next_score = explore.score+1000;
+        let turns = Direction::iter()
+            .filter(move |&dir| dir != current_dir && dir != current_dir.flip())
+            .map(move |dir| ((coo, dir), 1000));
 
-            let to_explore = Explore { loc: next_loc, score: next_score, previous: Some(explore.loc) };
-            pq.push(to_explore);
-        }
+        ahead.into_iter().chain(turns)
     }
 
     /// Solve the maze by searching for all the possible nearest paths that reach the end tile.
-    /// The function returns
-    /// * a [History] containing, for all the visited tiles, its score and its possible predecessors.
-    /// * The coordinate/direction of the arrival tile
-    fn solve (&self) -> (History, Location) {
-
-        // To keep track of visited locations
-        let mut visited = History::new();
-
-        // Dijkstra PQ, starting at the start location
-        let mut pq = PriorityQueue::new ();
-        let start = Explore { loc: self.start, score: 0, previous:None };
-        pq.push (start);
-
-        // Search loop
-        let mut arrival: Option<(Location, usize)> = None;
-        while let Some(explore) = pq.pop() {
-
-            // Update the history with the next element to explore. Skip it if we have
-            // already seen it.
-            let new_element = Self::update_history(&mut visited, &explore);
-            if !new_element { continue}
-
-            // Check for arrival. Record location and score but do not stop
-            if *self.tiles.sample(explore.loc.0) == MazeTile::End {
-                arrival = Some((explore.loc, explore.score));
+    /// The function returns the best score and the number of coordinates on one of the best paths.
+    fn solve (&self) -> Result<(Score, usize)> {
+
+        let mut search = Search::dijkstra([self.start], |&loc: &Location| self.next_locations(loc)).with_predecessors();
+
+        // Collect the arrival locations (any direction) reached with the best score,
+        // and stop when the remaining locations have worse scores
+        let mut best_score = None;
+        let mut arrivals = vec![];
+        for (loc, score) in search.by_ref() {
+            if best_score.is_some_and(|best_score| score > best_score) { break }
+            if *self.tiles.sample(loc.0) == MazeTile::End {
+                best_score = Some (score);
+                arrivals.push(loc);
             }
-
-            // Stop when we have found the arrival and when the queue only contains
-            // locations with worse scores
-            if let Some ((_loc, score)) = arrival {
-                if explore.score > score { break }
-            }
-
-            // Explore the location one step ahead
-            self.explore_ahead (&explore, &mut pq);
-
-            // Try the 90° rotations
-            self.explore_turns(&explore, &mut pq);
         }
 
-        let Some ((loc, _score)) = arrival else { panic!("No solution found")};
-        (visited, loc)
+        let best_score = best_score.ok_or(anyhow!("No solution found"))?;
+        let spots: HashSet<Coo> = search.states_on_paths_to(arrivals).into_iter().map(|(coo, _)| coo).collect();
+
+        Ok((best_score, spots.len()))
     }
 }
 
 /// Solve both parts of the puzzle
-fn solve (_content: &[&str]) -> Result<(usize, usize)> {
+fn solve (content: &[&str]) -> Result<(usize, usize)> {
 
     // Build and solve the maze
-    let maze = Maze::new(_content)?;
-    let (history, arrival_loc) = maze.solve();
+    let maze = Maze::new(content)?;
+    maze.solve()
+}
 
-    // Retrieve the path len from the history
-    let Some (arrival_entry) = history.get(&arrival_loc) else { bail!("No solution found") };
-    let path_len = arrival_entry.0;
 
-    // Count the number of coordinates that are on one of the best paths
-    let best_loc_count = Maze::count_best_paths_locations (arrival_loc, &history);
+pub fn day_16 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    Ok((path_len, best_loc_count))
+    let (ra, rb) = solve (content)?;
+    timer.lap(Phase::BothParts);
+    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub fn day_16 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST)).unwrap_or_default() == (7036, 45));
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST)).unwrap().0, 7036);
+    }
 
-    let (ra, rb) = solve (content)?;
-    Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST)).unwrap().1, 45);
+    }
+}
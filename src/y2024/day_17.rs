@@ -1,8 +1,11 @@
 use anyhow::*;
 use itertools::Itertools;
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST_1: &str = "\
 Register A: 729
 Register B: 0
@@ -10,6 +13,7 @@ Register C: 0
 
 Program: 0,1,5,4,3,0";
 
+#[cfg(test)]
 const TEST_2: &str = "\
 Register A: 2024
 Register B: 0
@@ -18,6 +22,7 @@ Register C: 0
 Program: 0,3,5,4,3,0";
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -376,13 +381,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(reg_a)
 }
 
-pub fn day_17 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST_1)).unwrap_or_default() == "4,6,3,5,6,3,5,2,1,0");
-    debug_assert!(part_b (&split(TEST_2)).unwrap_or_default() == 117440);
+pub fn day_17 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Text(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST_1)).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST_2)).unwrap(), 117440);
+    }
+}
@@ -1,9 +1,10 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap};
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
-use crate::tools::{Coo, Direction, IntReader};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
+use crate::tools::{Coo, IntReader, Search};
 
+#[cfg(test)]
 const TEST: &str = "\
 5,4
 4,2
@@ -32,8 +33,16 @@ const TEST: &str = "\
 2,0";
 
 /// Diffusion set for the alternate method of part 2 (same size of the memory space area)
+#[cfg(test)]
 type DiffuseSet = Vec<Vec<bool>>;
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::new("width", "71", "Width of the memory space"),
+    ParamSpec::new("height", "71", "Height of the memory space"),
+    ParamSpec::new("corruptions", "1024", "Number of bytes fallen when searching the shortest path"),
+];
+
 /// A tile of the memory space. When corrupted, we record at which time.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum MemoryTile {
@@ -45,30 +54,7 @@ struct MemorySpace {
     area: GridCell<MemoryTile>,
 }
 
-/// Next element to explore with Dijkstra
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-struct Explore {
-    coo: Coo,
-    score: usize,
-}
-
-/// Dijkstra priority queue
-type PriorityQueue = BinaryHeap<Explore>;
-
-/// Ordering for [Explore] elements in the [PriorityQueue]
-impl Ord for Explore {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
-    }
-}
-
-/// Ordering for [Explore] elements in the [PriorityQueue]
-impl PartialOrd for Explore {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -119,6 +105,7 @@ impl MemorySpace {
     }
 
     /// Return an iterator on the corruption coordinates, in the order they appear.
+    #[cfg(test)]
     fn get_corruption_it<'a> (content: &'a[&'a str]) -> impl DoubleEndedIterator<Item=Result<Coo>> +'a {
 
         let mut reader = IntReader::new(false);
@@ -143,45 +130,32 @@ impl MemorySpace {
         (self.area.width()-1, self.area.height()-1).into()
     }
 
-    /// Do a Dijkstra search to compute the number of steps required to reach the exit tile.
+    /// Do a A* search to compute the number of steps required to reach the exit tile.
     /// The parameter `num_corruptions` activates this first equivalent amount of blocks, other
     /// are ignored.
     fn compute_num_steps_to_exit (&self, num_corruptions: u32) -> Option<usize> {
 
-        let mut visited = vec![vec![false; self.area.height()]; self.area.width()];
         let exit = self.exit();
 
-        let mut pq = PriorityQueue::new ();
-        let start = Explore { coo: self.entry(), score: 0 };
-        pq.push (start);
-
-        while let Some (Explore { coo, score }) = pq.pop() {
-
-            if coo == exit { return Some(score); }
-
-            for dir in Direction::iter() {
-                let next_coo = coo.next(dir);
-                let nx = next_coo.x as usize;
-                let ny = next_coo.y as usize;
-
-                if let Some(tile) = self.area.try_sample(next_coo) {
-
-                    if let MemoryTile::Corrupted(time) = *tile {
-                        if time <= num_corruptions { continue; }
-                    }
-                    if visited[nx][ny] { continue; }
-
-                    visited[nx][ny] = true;
-                    pq.push(Explore { coo: next_coo, score: score +1 });
+        // Safe adjacent tiles, or corrupted later
+        let fn_next = |coo: &Coo| {
+            coo.iter_adjacent_4().filter(|&next_coo| {
+                match self.area.try_sample(next_coo) {
+                    Some (MemoryTile::Corrupted(time)) => *time > num_corruptions,
+                    Some (MemoryTile::Safe) => true,
+                    None => false,
                 }
-                else { continue; }
-            }
-        }
+            }).map(|next_coo| (next_coo, 1)).collect::<Vec<_>>()
+        };
+        let fn_heuristic = |coo: &Coo| coo.manhattan_distance(&exit) as usize;
 
-        None
+        Search::a_star([self.entry()], fn_next, fn_heuristic)
+            .find(|(coo, _)| *coo == exit)
+            .map(|(_, score)| score)
     }
 
     /// Extend a `set` of empty cells from the provided `coo`
+    #[cfg(test)]
     fn diffuse_from (&self, coo: Coo, set: &mut DiffuseSet) {
 
         // Init the queue with the initial coordinate
@@ -192,8 +166,7 @@ impl MemorySpace {
         while let Some (coo) = queue.pop() {
 
             // For each coordinate, check the 4 neighbors
-            for dir in Direction::iter() {
-                let next_coo = coo.next(dir);
+            for next_coo in coo.iter_adjacent_4() {
                 let x = next_coo.x as usize;
                 let y = next_coo.y as usize;
 
@@ -214,6 +187,7 @@ impl MemorySpace {
 /// Find the cutting block of part 2 with an alternate method. Here we expand two areas of
 /// empty cells from the start and the end. Then we remove the block one by one and check
 /// when both areas meet.
+#[cfg(test)]
 fn find_cutting_block (content: &[&str], space: &mut MemorySpace) -> Result<Coo> {
 
     // Build the diffusion sets from the start and the exit
@@ -303,6 +277,7 @@ fn part_b (content: &[&str], width: usize, height: usize, num_corruptions_start:
 
 /// Solve second part of the puzzle, with a memory space of size `width` x `height`.
 /// Use an alternative method based on diffusion areas extended from both the entry and the exit
+#[cfg(test)]
 fn part_b_alt(content: &[&str], width: usize, height: usize) -> Result<String> {
 
     // Instantiate the memory space with all the blocks
@@ -316,14 +291,44 @@ fn part_b_alt(content: &[&str], width: usize, height: usize) -> Result<String> {
     Ok(loc_string)
 }
 
-pub fn day_18 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_18 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    debug_assert!(part_a (&split(TEST), 7, 7, 12).unwrap_or_default() == 22);
-    debug_assert!(part_b (&split(TEST), 7, 7, 12).unwrap_or_default() == "6,1");
-    debug_assert!(part_b_alt(&split(TEST), 7, 7).unwrap_or_default() == "6,1");
-
-    let ra = part_a(content, 71, 71, 1024)?;
-    let rb = part_b(content, 71, 71, 1024)?;
+    let (width, height) = (params.get("width")?, params.get("height")?);
+    let num_corruptions = params.get("corruptions")?;
+    let ra = part_a(content, width, height, num_corruptions)?;
+    timer.lap(Phase::PartA);
+    let rb = part_b(content, width, height, num_corruptions)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Text(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST), 7, 7, 12).unwrap(), 22);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST), 7, 7, 12).unwrap(), "6,1");
+    }
+
+    #[test]
+    fn test_part_b_alt () {
+        assert_eq!(part_b_alt(&split(TEST), 7, 7).unwrap(), "6,1");
+    }
+
+    /// The parameters overridden on the command line reach the parts of the puzzle
+    #[test]
+    fn test_params () {
+        let overrides = [("width", "7"), ("height", "7"), ("corruptions", "12")].map(|(name, value)| (name.to_string(), value.to_string()));
+        let params = Params::with_overrides(PARAMS, &overrides).unwrap();
+        let (a, b) = day_18(&split(TEST), &params, &mut PhaseTimer::start()).unwrap();
+        assert_eq!(a.to_string(), "22");
+        assert_eq!(b.to_string(), "6,1");
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 
+#[cfg(test)]
 const TEST: &str = "\
 r, wr, b, g, bwu, rb, gb, br
 
@@ -31,6 +34,7 @@ type Designs<'a> = Vec<Design<'a>>;
 type Memo<'a> = HashMap<Design<'a>, usize>;
 
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -139,13 +143,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(count)
 }
 
-pub fn day_19 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 6);
-    debug_assert!(part_b (&split(TEST)).unwrap_or_default() == 16);
+pub fn day_19 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST)).unwrap(), 16);
+    }
+}
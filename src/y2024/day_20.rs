@@ -3,8 +3,11 @@ use std::collections::{HashMap};
 
 use anyhow::*;
 use crate::{Cell, GridCell, Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::{ParamSpec, Params};
 use crate::tools::{Coo, Direction};
 
+#[cfg(test)]
 const TEST: &str = "\
 ###############
 #...#...#.....#
@@ -22,6 +25,11 @@ const TEST: &str = "\
 #...#...#...###
 ###############";
 
+/// Tunable parameters of the puzzle
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::new("min_save", "100", "Minimum number of picoseconds a cheat must save to be counted"),
+];
+
 /// Models the different possible tiles in the [Maze],
 /// along with the time needed to reach them
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -87,6 +95,7 @@ impl Cell for MazeTile {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -215,12 +224,25 @@ fn solve (content: &[&str], min_time_save: u32) -> Result<(usize, usize)> {
     Ok((count_2, count_20))
 }
 
-pub fn day_20 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(solve (&split(TEST), 1).unwrap_or_default().0 == 44);
-    debug_assert!(solve (&split(TEST), 50).unwrap_or_default().1 == 285);
+pub fn day_20 (content: &[&str], params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
-    let (ra, rb) = solve(content, 100)?;
+    let (ra, rb) = solve(content, params.get("min_save")?)?;
+    timer.lap(Phase::BothParts);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(solve (&split(TEST), 1).unwrap().0, 44);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(solve (&split(TEST), 50).unwrap().1, 285);
+    }
+}
@@ -1,14 +1,18 @@
 use anyhow::*;
 use itertools::{Itertools};
 use crate::{Solution};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
 use crate::tools::IntReader;
 
+#[cfg(test)]
 const TEST: &str = "\
 1
 10
 100
 2024";
 
+#[cfg(test)]
 const TEST_2: &str = "\
 1
 2
@@ -22,6 +26,7 @@ type Sequence = (i8, i8, i8, i8);
 /// Banana sell price
 type Price = u8;
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -86,10 +91,10 @@ fn sequence_4_it(seed: usize) -> impl Iterator<Item=(Price, Sequence)> {
 /// Each sequence can thus be transformed into a unique number between 0 and 19^4=130321
 fn sequence_to_index (sequence: &Sequence) -> usize {
 
-    (sequence.0 as usize + 9) * 6859 +
-        (sequence.1 as usize + 9) * 361 +
-        (sequence.2 as usize + 9) * 19 +
-        (sequence.3 as usize + 9)
+    (sequence.0 + 9) as usize * 6859 +
+        (sequence.1 + 9) as usize * 361 +
+        (sequence.2 + 9) as usize * 19 +
+        (sequence.3 + 9) as usize
 }
 
 /// Solve first part of the puzzle
@@ -145,13 +150,27 @@ fn part_b (content: &[&str]) -> Result<usize> {
     Ok(best_price as usize)
 }
 
-pub fn day_22 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 37327623);
-    debug_assert!(part_b (&split(TEST_2)).unwrap_or_default() == 23);
+pub fn day_22 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Unsigned(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 37327623);
+    }
+
+    #[test]
+    fn test_part_b () {
+        assert_eq!(part_b (&split(TEST_2)).unwrap(), 23);
+    }
+}
@@ -3,8 +3,11 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use anyhow::*;
 use crate::{Solution};
-use crate::tools::{topo_sort, TopoSortElement};
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+use crate::tools::{topo_sort_kahn, TopoSortElement};
 
+#[cfg(test)]
 const TEST: &str = "\
 x00: 1
 x01: 1
@@ -17,7 +20,7 @@ x00 AND y00 -> z00
 x01 XOR y01 -> z01
 x02 OR y02 -> z02";
 
-// Gates form an acyclic graph of gates. They can be topologically sorted.
+// Gates form an acyclic graph of gates, unless badly swapped. They can be topologically sorted.
 impl TopoSortElement<GateName> for Gate {
     type Iter = std::vec::IntoIter<GateName>;
 
@@ -82,6 +85,7 @@ impl Gate {
     }
 }
 
+#[cfg(test)]
 fn split (content: &str) -> Vec<&str> {
     content.lines().collect()
 }
@@ -309,7 +313,7 @@ fn part_a (content: &[&str]) -> Result<usize> {
 
     // Load the circuit and compute the topological ordering
     let gates = load_gates(content)?;
-    let gate_names: Vec<GateName> = topo_sort(&gates);
+    let gate_names: Vec<GateName> = topo_sort_kahn(&gates)?;
 
     // Compute the circuit output
     let z = compute(&gates, &gate_names);
@@ -346,7 +350,7 @@ fn part_b (content: &[&str]) -> Result<String> {
     // Bonus, make some computation to check the result
     #[cfg(debug_assertions)]
     {
-        let topo_order: Vec<GateName> = topo_sort(&gates);
+        let topo_order: Vec<GateName> = topo_sort_kahn(&gates)?;
         set_x_y(&mut gates, 0x69696969, 0x42424242);
         debug_assert!(compute(&gates, &topo_order) == 0x69696969 + 0x42424242);
     }
@@ -355,12 +359,22 @@ fn part_b (content: &[&str]) -> Result<String> {
     Ok (errors.join(","))
 }
 
-pub fn day_24 (content: &[&str]) -> Result <(Solution, Solution)> {
-
-    debug_assert!(part_a (&split(TEST)).unwrap_or_default() == 4);
+pub fn day_24 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     let rb = part_b(content)?;
+    timer.lap(Phase::PartB);
 
     Ok((Solution::Unsigned(ra), Solution::Text(rb)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 4);
+    }
+}
@@ -1,5 +1,50 @@
 use anyhow::*;
 use crate::Solution;
+use crate::timing::{Phase, PhaseTimer};
+use crate::params::Params;
+
+#[cfg(test)]
+const TEST: &str = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
 
 /// A key, with its height in 5 positions
 type Key = [u8; 5];
@@ -7,6 +52,11 @@ type Key = [u8; 5];
 /// A Lock, with its height in 5 positions
 type Lock = [u8; 5];
 
+#[cfg(test)]
+fn split (content: &str) -> Vec<&str> {
+    content.lines().collect()
+}
+
 /// Given the 5 first chars in `row`, increment the `heights` at the corresponding
 /// positions for each encountered `#`.
 fn inc_height_from_row_it (heights: &mut [u8; 5], row: &str) {
@@ -95,8 +145,19 @@ fn part_a (content: &[&str]) -> Result<usize> {
     Ok(num_fits)
 }
 
-pub fn day_25 (content: &[&str]) -> Result <(Solution, Solution)> {
+pub fn day_25 (content: &[&str], _params: &Params, timer: &mut PhaseTimer) -> Result <(Solution, Solution)> {
 
     let ra = part_a(content)?;
+    timer.lap(Phase::PartA);
     Ok((Solution::Unsigned(ra), Solution::Unsigned(0)))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_a () {
+        assert_eq!(part_a (&split(TEST)).unwrap(), 3);
+    }
+}
@@ -25,6 +25,7 @@ mod day_24;
 mod day_25;
 
 use crate::{FnDay, Year};
+use crate::params::ParamSpec;
 
 pub struct Y2024;
 
@@ -81,5 +82,15 @@ impl Year for Y2024 {
             _ => None
         }
     }
-}
 
+    fn get_day_params(&self, day: u32) -> &'static [ParamSpec] {
+        match day {
+            6 => day_06::PARAMS,
+            14 => day_14::PARAMS,
+            15 => day_15::PARAMS,
+            18 => day_18::PARAMS,
+            20 => day_20::PARAMS,
+            _ => &[],
+        }
+    }
+}